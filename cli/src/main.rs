@@ -0,0 +1,284 @@
+use anyhow::{anyhow, Context, Result};
+use clap::{Parser, Subcommand};
+use solana_cli_config::{Config, CONFIG_FILE};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+	commitment_config::CommitmentConfig,
+	instruction::Instruction,
+	native_token::{lamports_to_sol, sol_to_lamports},
+	pubkey::Pubkey,
+	signature::{read_keypair_file, Keypair, Signature, Signer},
+	transaction::Transaction,
+};
+use todos::{ListItem, SeedScheme};
+use todos_client::{instruction, pda, List};
+
+/// Manage todos lists and bounties from the terminal.
+///
+/// The RPC URL and keypair default to the ones of the Solana CLI config.
+#[derive(Parser)]
+#[clap(name = "todos", version)]
+struct Cli {
+	/// Solana CLI config file to read the RPC URL and keypair from
+	#[clap(long, short = 'C', global = true)]
+	config: Option<String>,
+	/// RPC URL, overrides the config
+	#[clap(long, short = 'u', global = true)]
+	url: Option<String>,
+	/// Keypair file that signs and pays, overrides the config
+	#[clap(long, short = 'k', global = true)]
+	keypair: Option<String>,
+	#[clap(subcommand)]
+	command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+	/// Create and show lists
+	#[clap(subcommand)]
+	List(ListCommand),
+	/// Add, cancel and finish items.
+	/// Only lamport bounties without funders can be paid out from here,
+	/// token bounties and funded items need extra accounts
+	#[clap(subcommand)]
+	Item(ItemCommand),
+}
+
+#[derive(Subcommand)]
+enum ListCommand {
+	/// Create a list owned by the keypair
+	Create {
+		name: String,
+		/// Most open items the list may hold, no cap if omitted
+		#[clap(long)]
+		capacity: Option<u32>,
+		/// Settles disputes between the owner and item creators
+		#[clap(long)]
+		arbiter: Option<Pubkey>,
+		/// Seconds after the owner finished an item until they may claim it without the creator, needs an arbiter
+		#[clap(long, requires = "arbiter")]
+		review_window: Option<i64>,
+	},
+	/// Show a list and its items
+	Show {
+		name: String,
+		/// Creator of the list, defaults to the keypair
+		#[clap(long)]
+		owner: Option<Pubkey>,
+	},
+}
+
+#[derive(Subcommand)]
+enum ItemCommand {
+	/// Add an item, paying its bounty from the keypair
+	Add {
+		list: String,
+		name: String,
+		/// Bounty in SOL
+		#[clap(long)]
+		bounty: f64,
+		/// Creator of the list, defaults to the keypair
+		#[clap(long)]
+		owner: Option<Pubkey>,
+	},
+	/// Cancel an item, refunding its bounty to the item creator
+	Cancel {
+		list: String,
+		item: Pubkey,
+		/// Creator of the list, defaults to the keypair
+		#[clap(long)]
+		owner: Option<Pubkey>,
+	},
+	/// Mark an item finished, the bounty is paid once both sides finished
+	Finish {
+		list: String,
+		item: Pubkey,
+		/// Creator of the list, defaults to the keypair
+		#[clap(long)]
+		owner: Option<Pubkey>,
+	},
+	/// Take back the finish mark the keypair set on an item
+	Unfinish {
+		list: String,
+		item: Pubkey,
+		/// Creator of the list, defaults to the keypair
+		#[clap(long)]
+		owner: Option<Pubkey>,
+	},
+}
+
+struct Session {
+	rpc: RpcClient,
+	payer: Keypair,
+}
+
+impl Session {
+	fn new(cli: &Cli) -> Result<Self> {
+		let config = match cli.config.as_ref().or(CONFIG_FILE.as_ref()) {
+			Some(file) => Config::load(file).unwrap_or_default(),
+			None => Config::default(),
+		};
+
+		let url = cli.url.clone().unwrap_or(config.json_rpc_url);
+		let keypair_path = cli.keypair.clone().unwrap_or(config.keypair_path);
+		let payer = read_keypair_file(&keypair_path).map_err(|err| anyhow!("cannot read keypair {}: {}", keypair_path, err))?;
+
+		Ok(Session {
+			rpc: RpcClient::new_with_commitment(url, CommitmentConfig::confirmed()),
+			payer,
+		})
+	}
+
+	fn send(&self, instructions: &[Instruction]) -> Result<Signature> {
+		let blockhash = self.rpc.get_latest_blockhash()?;
+		let tx = Transaction::new_signed_with_payer(instructions, Some(&self.payer.pubkey()), &[&self.payer], blockhash);
+		Ok(self.rpc.send_and_confirm_transaction(&tx)?)
+	}
+
+	/// Loads the list `name` and its pages, falling back to the legacy truncated seed for lists created before hashed seeds.
+	fn list(&self, owner: Option<Pubkey>, name: &str) -> Result<(Pubkey, List)> {
+		let owner = owner.unwrap_or_else(|| self.payer.pubkey());
+
+		for scheme in [SeedScheme::Hashed, SeedScheme::Truncated] {
+			let (address, _) = pda::list(&owner, name, scheme);
+			match todos_client::fetch_list(&self.rpc, &address) {
+				Ok(list) => return Ok((address, list)),
+				Err(todos_client::Error::AccountNotFound(_)) => continue,
+				Err(err) => return Err(err).with_context(|| format!("cannot load list \"{}\"", name)),
+			}
+		}
+
+		Err(anyhow!("list \"{}\" of {} does not exist", name, owner))
+	}
+
+	/// Treasury of the program config, any account does while the config was not initialized.
+	fn treasury(&self, fallback: Pubkey) -> Result<Pubkey> {
+		match todos_client::fetch_config(&self.rpc) {
+			Ok(config) => Ok(config.treasury),
+			Err(todos_client::Error::AccountNotFound(_)) => Ok(fallback),
+			Err(err) => Err(err).context("cannot load the program config"),
+		}
+	}
+
+	fn item(&self, address: &Pubkey) -> Result<ListItem> {
+		todos_client::fetch_item(&self.rpc, address).with_context(|| format!("cannot load item {}", address))
+	}
+}
+
+/// Last item on the page of `item`, it moves into the slot of `item` when that is removed
+fn moved_item(list: &List, item: &Pubkey, item_data: &ListItem) -> Result<Pubkey> {
+	list.moved_item(item_data)
+		.ok_or_else(|| anyhow!("{} is not an open item of list \"{}\"", item, list.header.name()))
+}
+
+fn main() -> Result<()> {
+	let cli = Cli::parse();
+	let session = Session::new(&cli)?;
+	let user = session.payer.pubkey();
+
+	match cli.command {
+		Command::List(ListCommand::Create {
+			name,
+			capacity,
+			arbiter,
+			review_window,
+		}) => {
+			let ix = instruction::new_list(&user, &name, capacity, arbiter, review_window, SeedScheme::Hashed);
+			let signature = session.send(&[ix])?;
+			println!("Created list \"{}\" at {} ({})", name, pda::list(&user, &name, SeedScheme::Hashed).0, signature);
+		}
+		Command::List(ListCommand::Show { name, owner }) => {
+			let (address, list) = session.list(owner, &name)?;
+			let items = todos_client::fetch_items(&session.rpc, &list.lines())?;
+			print_list(&address, &list, &items);
+		}
+		Command::Item(ItemCommand::Add {
+			list,
+			name,
+			bounty,
+			owner,
+		}) => {
+			let (address, list) = session.list(owner, &list)?;
+			let (ix, item) = instruction::add(
+				&address,
+				&list.header,
+				list.next_page(),
+				&user,
+				&name,
+				sol_to_lamports(bounty),
+				None,
+				None,
+				[0; 32],
+			);
+			let signature = session.send(&[ix])?;
+			println!("Added item \"{}\" at {} ({})", name, item, signature);
+		}
+		Command::Item(ItemCommand::Cancel { list, item, owner }) => {
+			let (address, list) = session.list(owner, &list)?;
+			let item_data = session.item(&item)?;
+			let moved_item = moved_item(&list, &item, &item_data)?;
+			let ix = instruction::cancel(&address, &list.header, &item, &item_data, &moved_item, &user);
+			let signature = session.send(&[ix])?;
+			println!("Cancelled item \"{}\", bounty refunded to {} ({})", item_data.name, item_data.creator, signature);
+		}
+		Command::Item(ItemCommand::Finish { list, item, owner }) => {
+			let (address, list) = session.list(owner, &list)?;
+			let item_data = session.item(&item)?;
+			let moved_item = moved_item(&list, &item, &item_data)?;
+			let treasury = session.treasury(list.header.list_owner)?;
+			let ix = instruction::finish(&address, &list.header, &item, &item_data, &moved_item, &treasury, &user);
+			let signature = session.send(&[ix])?;
+
+			// Items are closed once the bounty is paid
+			if session.rpc.get_account_with_commitment(&item, session.rpc.commitment())?.value.is_none() {
+				println!("Finished item \"{}\", bounty paid to {} ({})", item_data.name, list.header.list_owner, signature);
+			} else {
+				println!("Marked item \"{}\" finished, waiting for the other side ({})", item_data.name, signature);
+			}
+		}
+		Command::Item(ItemCommand::Unfinish { list, item, owner }) => {
+			let (address, list) = session.list(owner, &list)?;
+			let item_data = session.item(&item)?;
+			let ix = instruction::unfinish(&address, &list.header, &item, &user);
+			let signature = session.send(&[ix])?;
+			println!("Took back finish mark on item \"{}\" ({})", item_data.name, signature);
+		}
+	}
+
+	Ok(())
+}
+
+fn print_list(address: &Pubkey, list: &List, items: &[(Pubkey, ListItem)]) {
+	println!("{} ({})", list.header.name(), address);
+	println!("owner: {}", list.header.list_owner);
+	match list.header.capacity {
+		0 => println!("items: {}", items.len()),
+		capacity => println!("items: {}/{}", items.len(), capacity),
+	}
+
+	for (address, item) in items {
+		let bounty = match item.bounty_mint {
+			Some(mint) => format!("{} tokens of {}", item.bounty_amount, mint),
+			None => format!("{} SOL", lamports_to_sol(item.bounty_amount)),
+		};
+		let disputed = if item.disputed { "  disputed" } else { "" };
+
+		println!();
+		println!("#{} {} ({})", item.index, item.name, address);
+		println!(
+			"    {}  {} creator  {} owner{}",
+			bounty,
+			flag(item.creator_finished),
+			flag(item.list_owner_finished),
+			disputed
+		);
+	}
+}
+
+fn flag(finished: bool) -> &'static str {
+	if finished {
+		"✔"
+	} else {
+		"✘"
+	}
+}
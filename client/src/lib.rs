@@ -0,0 +1,334 @@
+//! Rust client for the todos program: PDA derivation, instruction builders and typed account fetching.
+//!
+//! Builders only add the accounts named in the program's `Accounts` structs. Token bounties and
+//! funded items expect extra `remaining_accounts`, append them to `Instruction::accounts` as documented
+//! on the program's `finish` and `cancel` instructions.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{instruction::Instruction, system_program};
+use anchor_lang::{AccountDeserialize, InstructionData, ZeroCopy};
+use solana_client::client_error::ClientError;
+use solana_client::rpc_client::RpcClient;
+
+pub use todos::{list_seed, name_seed, Config, ListHeader, ListItem, ListPage, SeedScheme, ID, PAGE_SIZE};
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+	#[error("rpc request failed: {0}")]
+	Rpc(Box<ClientError>),
+	#[error("account {0} does not exist")]
+	AccountNotFound(Pubkey),
+	#[error("account could not be deserialized: {0}")]
+	Deserialize(Box<anchor_lang::error::Error>),
+}
+
+impl From<ClientError> for Error {
+	fn from(err: ClientError) -> Self {
+		Error::Rpc(Box::new(err))
+	}
+}
+
+impl From<anchor_lang::error::Error> for Error {
+	fn from(err: anchor_lang::error::Error) -> Self {
+		Error::Deserialize(Box::new(err))
+	}
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+pub mod pda {
+	use super::*;
+
+	/// Address of the list `name` created by `seed_owner`, lists keep it when they change owner
+	pub fn list(seed_owner: &Pubkey, name: &str, scheme: SeedScheme) -> (Pubkey, u8) {
+		Pubkey::find_program_address(&[b"todolist", seed_owner.as_ref(), &list_seed(name, scheme)], &ID)
+	}
+
+	/// Address of the `index`th item ever added to `list`
+	pub fn item(list: &Pubkey, index: u64) -> (Pubkey, u8) {
+		Pubkey::find_program_address(&[b"item", list.as_ref(), &index.to_le_bytes()], &ID)
+	}
+
+	/// Page `page_no` holding open items of `list`
+	pub fn page(list: &Pubkey, page_no: u32) -> (Pubkey, u8) {
+		Pubkey::find_program_address(&[b"page", list.as_ref(), &page_no.to_le_bytes()], &ID)
+	}
+
+	/// Settings of the deployment, the account only exists once the admin initialized it
+	pub fn config() -> (Pubkey, u8) {
+		Pubkey::find_program_address(&[b"config"], &ID)
+	}
+
+	/// Roles of `user` on `list`, the account only exists once they were made a moderator
+	pub fn moderator(list: &Pubkey, user: &Pubkey) -> (Pubkey, u8) {
+		Pubkey::find_program_address(&[b"moderator", list.as_ref(), user.as_ref()], &ID)
+	}
+}
+
+pub mod instruction {
+	use super::*;
+
+	/// Creates the list `name` owned by `owner`, who pays for it. Lists without `capacity` have no item cap.
+	pub fn new_list(
+		owner: &Pubkey,
+		name: &str,
+		capacity: Option<u32>,
+		arbiter: Option<Pubkey>,
+		review_window: Option<i64>,
+		seed_scheme: SeedScheme,
+	) -> Instruction {
+		let (list, _) = pda::list(owner, name, seed_scheme);
+
+		Instruction {
+			program_id: ID,
+			accounts: todos::accounts::NewList {
+				list,
+				system_program: system_program::ID,
+				user: *owner,
+				config: pda::config().0,
+			}
+			.to_account_metas(None),
+			data: todos::instruction::NewList {
+				name: name.to_string(),
+				capacity,
+				arbiter,
+				review_window,
+				seed_scheme,
+			}
+			.data(),
+		}
+	}
+
+	/// Adds an item with a lamport `bounty` paid by `user`, returning the instruction and the new item's address.
+	/// `list_data` must be current, the item address is derived from its item count.
+	/// `page_no` is the page the item is stored on, see [`List::next_page`](crate::List::next_page).
+	#[allow(clippy::too_many_arguments)]
+	pub fn add(
+		list: &Pubkey,
+		list_data: &ListHeader,
+		page_no: u32,
+		user: &Pubkey,
+		item_name: &str,
+		bounty: u64,
+		deadline: Option<i64>,
+		uri: Option<String>,
+		content_hash: [u8; 32],
+	) -> (Instruction, Pubkey) {
+		let (item, _) = pda::item(list, list_data.item_count);
+
+		let ix = Instruction {
+			program_id: ID,
+			accounts: todos::accounts::Add {
+				list: *list,
+				list_owner: list_data.list_owner,
+				page: pda::page(list, page_no).0,
+				item,
+				system_program: system_program::ID,
+				user: *user,
+				config: pda::config().0,
+			}
+			.to_account_metas(None),
+			data: todos::instruction::Add {
+				_list_name: list_data.name().to_string(),
+				item_name: item_name.to_string(),
+				bounty,
+				deadline,
+				uri,
+				content_hash,
+				page_no,
+			}
+			.data(),
+		};
+
+		(ix, item)
+	}
+
+	/// Cancels `item` on behalf of `user`, refunding the bounty to the item creator.
+	/// `moved_item` is the last item on the page of `item`, see [`List::moved_item`](crate::List::moved_item).
+	pub fn cancel(
+		list: &Pubkey,
+		list_data: &ListHeader,
+		item: &Pubkey,
+		item_data: &ListItem,
+		moved_item: &Pubkey,
+		user: &Pubkey,
+	) -> Instruction {
+		Instruction {
+			program_id: ID,
+			accounts: todos::accounts::Cancel {
+				list: *list,
+				list_owner: list_data.list_owner,
+				page: pda::page(list, item_data.page_no).0,
+				item: *item,
+				moved_item: *moved_item,
+				item_creator: item_data.creator,
+				user: *user,
+				moderator: pda::moderator(list, user).0,
+				config: pda::config().0,
+			}
+			.to_account_metas(None),
+			data: todos::instruction::Cancel {
+				_list_name: list_data.name().to_string(),
+			}
+			.data(),
+		}
+	}
+
+	/// Marks `item` finished on behalf of `user`, the bounty is paid once both sides finished.
+	/// `moved_item` is the last item on the page of `item`, it takes over the slot once the item is paid.
+	/// `treasury` receives the fee and must be the one of the [`Config`], any account is accepted while there is none.
+	pub fn finish(
+		list: &Pubkey,
+		list_data: &ListHeader,
+		item: &Pubkey,
+		item_data: &ListItem,
+		moved_item: &Pubkey,
+		treasury: &Pubkey,
+		user: &Pubkey,
+	) -> Instruction {
+		Instruction {
+			program_id: ID,
+			accounts: todos::accounts::Finish {
+				list: *list,
+				list_owner: list_data.list_owner,
+				page: pda::page(list, item_data.page_no).0,
+				item: *item,
+				moved_item: *moved_item,
+				user: *user,
+				moderator: pda::moderator(list, user).0,
+				config: pda::config().0,
+				treasury: *treasury,
+			}
+			.to_account_metas(None),
+			data: todos::instruction::Finish {
+				_list_name: list_data.name().to_string(),
+			}
+			.data(),
+		}
+	}
+
+	/// Clears the finish mark `user` set on `item`.
+	pub fn unfinish(list: &Pubkey, list_data: &ListHeader, item: &Pubkey, user: &Pubkey) -> Instruction {
+		Instruction {
+			program_id: ID,
+			accounts: todos::accounts::Unfinish {
+				list: *list,
+				list_owner: list_data.list_owner,
+				item: *item,
+				user: *user,
+				moderator: pda::moderator(list, user).0,
+				config: pda::config().0,
+			}
+			.to_account_metas(None),
+			data: todos::instruction::Unfinish {
+				_list_name: list_data.name().to_string(),
+			}
+			.data(),
+		}
+	}
+}
+
+/// Deserializes account data of a Borsh encoded todos account, checking its discriminator.
+/// Lists and their pages are zero-copy, use [`deserialize_zero_copy`] for them.
+pub fn deserialize<T: AccountDeserialize>(data: &[u8]) -> Result<T> {
+	Ok(T::try_deserialize(&mut &data[..])?)
+}
+
+/// Deserializes account data of a zero-copy todos account, checking its discriminator.
+pub fn deserialize_zero_copy<T: ZeroCopy>(data: &[u8]) -> Result<T> {
+	let size = std::mem::size_of::<T>();
+	if data.len() < 8 + size {
+		return Err(error!(anchor_lang::error::ErrorCode::AccountDidNotDeserialize).into());
+	}
+	if data[..8] != T::discriminator() {
+		return Err(error!(anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch).into());
+	}
+	Ok(bytemuck::pod_read_unaligned(&data[8..8 + size]))
+}
+
+fn fetch_data(rpc: &RpcClient, address: &Pubkey) -> Result<Vec<u8>> {
+	let account = rpc
+		.get_account_with_commitment(address, rpc.commitment())?
+		.value
+		.ok_or(Error::AccountNotFound(*address))?;
+	Ok(account.data)
+}
+
+pub fn fetch<T: AccountDeserialize>(rpc: &RpcClient, address: &Pubkey) -> Result<T> {
+	deserialize(&fetch_data(rpc, address)?)
+}
+
+/// A list header together with its pages, in page order.
+#[derive(Clone)]
+pub struct List {
+	pub header: ListHeader,
+	pub pages: Vec<ListPage>,
+}
+
+impl List {
+	/// Addresses of the open items across all pages
+	pub fn lines(&self) -> Vec<Pubkey> {
+		self.pages.iter().flat_map(|page| page.items()).copied().collect()
+	}
+
+	/// Last item on the page of `item`, it moves into the slot of `item` when that is removed
+	pub fn moved_item(&self, item: &ListItem) -> Option<Pubkey> {
+		self.pages.get(item.page_no as usize)?.items().last().copied()
+	}
+
+	/// Page the next item is added to, the last page until it is full
+	pub fn next_page(&self) -> u32 {
+		match self.pages.last() {
+			Some(page) if (page.len as usize) < PAGE_SIZE => page.page_no,
+			_ => self.header.page_count,
+		}
+	}
+}
+
+/// Fetches the list header and all of its pages.
+pub fn fetch_list(rpc: &RpcClient, address: &Pubkey) -> Result<List> {
+	let header: ListHeader = deserialize_zero_copy(&fetch_data(rpc, address)?)?;
+	let addresses: Vec<Pubkey> = (0..header.page_count).map(|page_no| pda::page(address, page_no).0).collect();
+
+	let mut pages = Vec::with_capacity(addresses.len());
+	for (_, data) in fetch_multiple(rpc, &addresses)? {
+		pages.push(deserialize_zero_copy(&data)?);
+	}
+
+	Ok(List { header, pages })
+}
+
+/// Fetches the config of the deployment, [`Error::AccountNotFound`] until the admin initialized it.
+pub fn fetch_config(rpc: &RpcClient) -> Result<Config> {
+	fetch(rpc, &pda::config().0)
+}
+
+pub fn fetch_item(rpc: &RpcClient, address: &Pubkey) -> Result<ListItem> {
+	fetch(rpc, address)
+}
+
+/// Most accounts a single `getMultipleAccounts` request may ask for
+const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+
+/// Fetches the data of `addresses` in as few requests as possible, failing if any of them does not exist.
+fn fetch_multiple(rpc: &RpcClient, addresses: &[Pubkey]) -> Result<Vec<(Pubkey, Vec<u8>)>> {
+	let mut data = Vec::with_capacity(addresses.len());
+
+	for chunk in addresses.chunks(MAX_MULTIPLE_ACCOUNTS) {
+		let accounts = rpc.get_multiple_accounts(chunk)?;
+		for (address, account) in chunk.iter().zip(accounts) {
+			let account = account.ok_or(Error::AccountNotFound(*address))?;
+			data.push((*address, account.data));
+		}
+	}
+
+	Ok(data)
+}
+
+/// Fetches the open items `lines` of a list, in page order.
+pub fn fetch_items(rpc: &RpcClient, lines: &[Pubkey]) -> Result<Vec<(Pubkey, ListItem)>> {
+	fetch_multiple(rpc, lines)?
+		.into_iter()
+		.map(|(address, data)| Ok((address, deserialize(&data)?)))
+		.collect()
+}
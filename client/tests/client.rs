@@ -0,0 +1,135 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::Discriminator;
+use bytemuck::Zeroable;
+use todos_client::{deserialize, deserialize_zero_copy, instruction, pda, List, ListHeader, ListItem, ListPage, SeedScheme, PAGE_SIZE};
+
+fn list_data(owner: Pubkey, name: &str, item_count: u64) -> ListHeader {
+	let mut list = ListHeader::zeroed();
+	list.list_owner = owner;
+	list.seed_owner = owner;
+	list.seed_scheme = SeedScheme::Hashed as u8;
+	list.capacity = 16;
+	list.bump = pda::list(&owner, name, SeedScheme::Hashed).1;
+	list.item_count = item_count;
+	list.name[..name.len()].copy_from_slice(name.as_bytes());
+	list.name_len = name.len() as u16;
+	list
+}
+
+#[test]
+fn legacy_list_names_are_truncated_to_the_seed_limit() {
+	let owner = Pubkey::new_unique();
+	let name = "A list with a name longer than thirty-two bytes";
+
+	assert_eq!(pda::list(&owner, name, SeedScheme::Truncated), pda::list(&owner, &name[..32], SeedScheme::Truncated));
+	assert_ne!(pda::list(&owner, name, SeedScheme::Truncated), pda::list(&owner, &name[..31], SeedScheme::Truncated));
+}
+
+#[test]
+fn hashed_list_names_use_the_full_name() {
+	let owner = Pubkey::new_unique();
+	let q1 = pda::list(&owner, "Quarterly infrastructure roadmap - Q1", SeedScheme::Hashed);
+	let q2 = pda::list(&owner, "Quarterly infrastructure roadmap - Q2", SeedScheme::Hashed);
+
+	assert_ne!(q1, q2);
+}
+
+#[test]
+fn add_derives_the_next_item_from_the_item_count() {
+	let owner = Pubkey::new_unique();
+	let (list, _) = pda::list(&owner, "list", SeedScheme::Hashed);
+	let list_data = list_data(owner, "list", 7);
+
+	let (ix, item) = instruction::add(&list, &list_data, 0, &owner, "An item", 1_000_000_000, None, None, [0; 32]);
+
+	assert_eq!(item, pda::item(&list, 7).0);
+	assert_eq!(ix.accounts[0].pubkey, list);
+	assert_eq!(ix.accounts[2].pubkey, pda::page(&list, 0).0);
+	assert_eq!(ix.accounts[3].pubkey, item);
+	assert!(ix.accounts[3].is_writable);
+	assert!(ix.accounts[5].is_signer);
+}
+
+fn page(list: Pubkey, page_no: u32, items: &[Pubkey]) -> ListPage {
+	let mut page = ListPage::zeroed();
+	page.list = list;
+	page.page_no = page_no;
+	page.len = items.len() as u32;
+	page.lines[..items.len()].copy_from_slice(items);
+	page
+}
+
+#[test]
+fn lists_round_trip_through_deserialize_zero_copy() {
+	let owner = Pubkey::new_unique();
+	let list_data = list_data(owner, "list", 3);
+
+	let mut data = ListHeader::discriminator().to_vec();
+	data.extend_from_slice(bytemuck::bytes_of(&list_data));
+
+	let list: ListHeader = deserialize_zero_copy(&data).unwrap();
+	assert_eq!(list.name(), "list");
+	assert_eq!(list.item_count, 3);
+
+	assert!(deserialize::<ListItem>(&data).is_err(), "Discriminator is checked");
+	assert!(deserialize_zero_copy::<ListPage>(&data).is_err(), "Discriminator is checked");
+	assert!(deserialize_zero_copy::<ListHeader>(&data[..100]).is_err(), "Truncated data is rejected");
+}
+
+#[test]
+fn lists_of_earlier_versions_are_rejected() {
+	let mut data = hash(b"account:TodoList").to_bytes()[..8].to_vec();
+	data.extend_from_slice(bytemuck::bytes_of(&list_data(Pubkey::new_unique(), "list", 3)));
+
+	assert!(deserialize_zero_copy::<ListHeader>(&data).is_err(), "Discriminator of the old layout is rejected");
+}
+
+fn item_data(list: Pubkey, page_no: u32, slot: u32) -> ListItem {
+	ListItem {
+		creator: Pubkey::new_unique(),
+		index: 0,
+		creator_finished: false,
+		list_owner_finished: false,
+		owner_finished_at: None,
+		bounty_mint: None,
+		bounty_amount: 1_000_000_000,
+		fee_bps: 0,
+		deadline: None,
+		disputed: false,
+		funded_amount: 0,
+		funders: 0,
+		name: "An item".to_string(),
+		uri: None,
+		content_hash: [0; 32],
+		list,
+		page_no,
+		slot,
+	}
+}
+
+#[test]
+fn lists_add_to_the_last_page_until_it_is_full() {
+	let owner = Pubkey::new_unique();
+	let (address, _) = pda::list(&owner, "list", SeedScheme::Hashed);
+	let first: Vec<Pubkey> = (0..PAGE_SIZE).map(|_| Pubkey::new_unique()).collect();
+	let item = Pubkey::new_unique();
+
+	let mut list = List {
+		header: list_data(owner, "list", PAGE_SIZE as u64),
+		pages: vec![],
+	};
+	assert_eq!(list.next_page(), 0, "First item opens the first page");
+
+	list.header.page_count = 1;
+	list.pages.push(page(address, 0, &first));
+	assert_eq!(list.next_page(), 1, "Full page is followed by the next page");
+
+	list.header.page_count = 2;
+	list.pages.push(page(address, 1, &[item]));
+	assert_eq!(list.next_page(), 1, "Last page has free slots");
+	assert_eq!(list.moved_item(&item_data(address, 0, 3)), Some(first[PAGE_SIZE - 1]), "Last item of the page moves");
+	assert_eq!(list.moved_item(&item_data(address, 1, 0)), Some(item), "Last item moves into its own slot");
+	assert_eq!(list.moved_item(&item_data(address, 2, 0)), None, "Page does not exist");
+	assert_eq!(list.lines().len(), PAGE_SIZE + 1, "Open items of all pages are listed");
+}
@@ -1,6 +1,10 @@
 use anchor_lang::error_code;
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+use anchor_lang::solana_program::hash::hash;
 use anchor_lang::AccountsClose;
+use anchor_lang::Discriminator;
+use anchor_spl::token::{self, CloseAccount, Mint, Token, TokenAccount, Transfer};
 
 declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
 
@@ -9,26 +13,82 @@ pub mod todos {
 	use anchor_lang::solana_program::{program::invoke, system_instruction::transfer};
 
 	use super::*;
-	pub fn new_list(ctx: Context<NewList>, name: String, capacity: u16, account_bump: u8) -> Result<()> {
+	pub fn new_list(
+		ctx: Context<NewList>,
+		name: String,
+		capacity: Option<u32>,
+		arbiter: Option<Pubkey>,
+		review_window: Option<i64>,
+		seed_scheme: SeedScheme,
+	) -> Result<()> {
+		check_not_paused(&ctx.accounts.config, Operation::Other)?;
+		check_list_name(&name, seed_scheme)?;
+		require!(capacity != Some(0), ErrorCode::CapacityTooSmall);
+		if let Some(review_window) = review_window {
+			require!(review_window > 0, ErrorCode::InvalidReviewWindow);
+			// Creators object to a claim by disputing it, which only an arbiter can settle
+			require!(arbiter.is_some(), ErrorCode::ReviewWindowNeedsArbiter);
+		}
+		// An owner settling disputes over their own list could award themselves every bounty
+		require!(arbiter != Some(ctx.accounts.user.key()), ErrorCode::OwnerIsArbiter);
+
 		// Create a new account
-		let list = &mut ctx.accounts.list;
+		let mut list = ctx.accounts.list.load_init()?;
 		list.list_owner = *ctx.accounts.user.key;
-		list.name = name;
-		list.capacity = capacity;
-		list.bump = account_bump;
+		list.seed_owner = *ctx.accounts.user.key;
+		list.seed_scheme = seed_scheme as u8;
+		list.name[..name.len()].copy_from_slice(name.as_bytes());
+		list.name_len = name.len() as u16;
+		list.capacity = capacity.unwrap_or_default();
+		// Store the canonical bump Anchor found, later instructions verify the address with it
+		list.bump = *ctx.bumps.get("list").unwrap();
+		list.arbiter = arbiter.unwrap_or_default();
+		list.review_window = review_window.unwrap_or_default();
+
+		emit!(ListCreated {
+			list: ctx.accounts.list.key(),
+			list_owner: list.list_owner,
+			name,
+			capacity,
+		});
+
 		Ok(())
 	}
 
-	pub fn add(ctx: Context<Add>, _list_name: String, item_name: String, bounty: u64) -> Result<()> {
+	pub fn add(
+		ctx: Context<Add>,
+		_list_name: String,
+		item_name: String,
+		bounty: u64,
+		deadline: Option<i64>,
+		uri: Option<String>,
+		content_hash: [u8; 32],
+		page_no: u32,
+	) -> Result<()> {
+		check_not_paused(&ctx.accounts.config, Operation::Deposit)?;
 		let user = &ctx.accounts.user;
-		let list = &mut ctx.accounts.list;
 		let item = &mut ctx.accounts.item;
 
-		require!(list.lines.len() < list.capacity as usize, ErrorCode::ListFull);
+		push_line(&ctx.accounts.list, &ctx.accounts.page, page_no, user.key(), item)?;
+		check_deadline(deadline)?;
+		check_uri(&uri)?;
 
-		list.lines.push(*item.to_account_info().key);
 		item.name = item_name;
+		item.uri = uri;
+		item.content_hash = content_hash;
 		item.creator = *user.to_account_info().key;
+		item.bounty_mint = None;
+		item.bounty_amount = bounty;
+		item.fee_bps = load_config(&ctx.accounts.config)?.map_or(0, |config| config.fee_bps);
+		item.deadline = deadline;
+
+		emit!(ItemAdded {
+			list: ctx.accounts.list.key(),
+			item: item.key(),
+			creator: item.creator,
+			bounty_mint: None,
+			bounty,
+		});
 
 		// Move the bounty to the account.
 		// We account for the rent amount that Anchor's init already transferred into the account.
@@ -49,130 +109,1596 @@ pub mod todos {
 		Ok(())
 	}
 
-	pub fn cancel(ctx: Context<Cancel>, _list_name: String) -> Result<()> {
-		let list = &mut ctx.accounts.list;
+	pub fn add_token(
+		ctx: Context<AddToken>,
+		_list_name: String,
+		item_name: String,
+		amount: u64,
+		deadline: Option<i64>,
+		uri: Option<String>,
+		content_hash: [u8; 32],
+		page_no: u32,
+	) -> Result<()> {
+		check_not_paused(&ctx.accounts.config, Operation::Deposit)?;
+		let item = &mut ctx.accounts.item;
+
+		push_line(&ctx.accounts.list, &ctx.accounts.page, page_no, ctx.accounts.user.key(), item)?;
+		require!(amount > 0, ErrorCode::BountyTooSmall);
+		check_deadline(deadline)?;
+		check_uri(&uri)?;
+
+		item.name = item_name;
+		item.uri = uri;
+		item.content_hash = content_hash;
+		item.creator = *ctx.accounts.user.key;
+		item.bounty_mint = Some(ctx.accounts.mint.key());
+		item.bounty_amount = amount;
+		item.deadline = deadline;
+
+		emit!(ItemAdded {
+			list: ctx.accounts.list.key(),
+			item: item.key(),
+			creator: item.creator,
+			bounty_mint: item.bounty_mint,
+			bounty: amount,
+		});
+
+		// Move the tokens into the escrow owned by the item's escrow authority.
+		// Anchor's init already covered the rent of both the item and the escrow.
+		token::transfer(
+			CpiContext::new(
+				ctx.accounts.token_program.to_account_info(),
+				Transfer {
+					from: ctx.accounts.user_token.to_account_info(),
+					to: ctx.accounts.escrow.to_account_info(),
+					authority: ctx.accounts.user.to_account_info(),
+				},
+			),
+			amount,
+		)?;
+
+		Ok(())
+	}
+
+	pub fn cancel<'info>(ctx: Context<'_, '_, '_, 'info, Cancel<'info>>, _list_name: String) -> Result<()> {
+		check_not_paused(&ctx.accounts.config, Operation::Refund)?;
 		let item = &mut ctx.accounts.item;
 		let item_creator = &ctx.accounts.item_creator;
 
 		let user = ctx.accounts.user.to_account_info().key;
+		// has_one ties the list owner account to the list
+		let is_list_owner = ctx.accounts.list_owner.key == user;
 
-		require!(
-			&list.list_owner == user || &item.creator == user,
-			ErrorCode::CancelPermissions
-		);
-		require!(list.lines.contains(item.to_account_info().key), ErrorCode::ItemNotFound);
+		if !is_list_owner && &item.creator != user {
+			let moderator = load_moderator(&ctx.accounts.moderator)?.ok_or(ErrorCode::CancelPermissions)?;
+			require!(moderator.can_cancel, ErrorCode::ModeratorCannotCancel);
+		} else if !is_list_owner && ctx.accounts.list.load()?.arbiter != Pubkey::default() {
+			// Once the work is delivered the creator has to dispute it instead of taking the bounty back
+			require!(!item.list_owner_finished, ErrorCode::OwnerAlreadyFinished);
+		}
+		remove_line(&ctx.accounts.list, &ctx.accounts.page, item, &ctx.accounts.moved_item)?;
+		require!(!item.disputed, ErrorCode::ItemDisputed);
+
+		emit!(ItemCancelled {
+			list: ctx.accounts.list.key(),
+			item: item.key(),
+			cancelled_by: *user,
+			refund_recipient: item.creator,
+		});
+
+		// Return the tokens to the item creator and any other funders
+		refund_bounty(item, &item_creator.to_account_info(), ctx.remaining_accounts)
+	}
+
+	pub fn expire<'info>(ctx: Context<'_, '_, '_, 'info, Expire<'info>>, _list_name: String) -> Result<()> {
+		check_not_paused(&ctx.accounts.config, Operation::Refund)?;
+		let item = &mut ctx.accounts.item;
+
+		remove_line(&ctx.accounts.list, &ctx.accounts.page, item, &ctx.accounts.moved_item)?;
+		require!(!item.disputed, ErrorCode::ItemDisputed);
+		// Delivered work is settled by finishing or disputing it, the deadline only covers undelivered items.
+		// Without an arbiter there is nobody to dispute to, so the deadline still applies
+		if ctx.accounts.list.load()?.arbiter != Pubkey::default() {
+			require!(!item.list_owner_finished, ErrorCode::OwnerAlreadyFinished);
+		}
+
+		let deadline = item.deadline.ok_or(ErrorCode::NoDeadline)?;
+		require!(Clock::get()?.unix_timestamp >= deadline, ErrorCode::DeadlineNotReached);
+
+		emit!(ItemExpired {
+			list: ctx.accounts.list.key(),
+			item: item.key(),
+			refund_recipient: item.creator,
+		});
+
+		// Anyone may expire an item, the bounty always goes back to its creator
+		refund_bounty(item, &ctx.accounts.item_creator.to_account_info(), ctx.remaining_accounts)
+	}
+
+	/// Replaces the name and description of an item, reallocating it to fit. The item creator pays any extra rent.
+	pub fn edit_item(
+		ctx: Context<EditItem>,
+		_list_name: String,
+		item_name: String,
+		uri: Option<String>,
+		content_hash: [u8; 32],
+	) -> Result<()> {
+		check_not_paused(&ctx.accounts.config, Operation::Other)?;
+		let item = &mut ctx.accounts.item;
+		let item_creator = &ctx.accounts.item_creator;
+
+		require!(!item.creator_finished && !item.list_owner_finished, ErrorCode::ItemLocked);
+		require!(!item.disputed, ErrorCode::ItemDisputed);
+		// Funders contributed to the item as it was described then
+		require!(item.funders == 0, ErrorCode::ItemHasFunders);
+		check_uri(&uri)?;
+
+		let space = ListItem::space(&item_name, &uri);
+		let rent_exempt = Rent::get()?.minimum_balance(space);
+		let item_info = item.to_account_info();
+		let item_lamports = item_info.lamports();
+
+		// Shrinking leaves the lamports on the item, they are part of the bounty
+		if rent_exempt > item_lamports {
+			invoke(
+				&transfer(item_creator.key, item_info.key, rent_exempt - item_lamports),
+				&[
+					item_creator.to_account_info(),
+					item_info.clone(),
+					ctx.accounts.system_program.to_account_info(),
+				],
+			)?;
+		}
 
-		// Return the tokens to the item creator
-		item.close(item_creator.to_account_info())?;
+		item_info.realloc(space, false)?;
+		item.name = item_name;
+		item.uri = uri;
+		item.content_hash = content_hash;
 
-		let item_key = ctx.accounts.item.to_account_info().key;
-		list.lines.retain(|key| key != item_key);
+		emit!(ItemEdited {
+			list: ctx.accounts.list.key(),
+			item: item.key(),
+			name: item.name.clone(),
+			uri: item.uri.clone(),
+			content_hash,
+		});
 
 		Ok(())
 	}
 
-	pub fn finish(ctx: Context<Finish>, _list_name: String) -> Result<()> {
+	/// Adds `amount` lamports to the bounty of an existing item.
+	/// Every funder gets a contribution receipt so cancelling can refund them.
+	pub fn fund(ctx: Context<Fund>, _list_name: String, amount: u64) -> Result<()> {
+		check_not_paused(&ctx.accounts.config, Operation::Deposit)?;
 		let item = &mut ctx.accounts.item;
-		let list = &mut ctx.accounts.list;
+		let contribution = &mut ctx.accounts.contribution;
+		let funder = &ctx.accounts.funder;
+
+		require!(item.bounty_mint.is_none(), ErrorCode::UnsupportedTokenBounty);
+		require!(!item.disputed, ErrorCode::ItemDisputed);
+		require!(amount > 0, ErrorCode::BountyTooSmall);
+
+		// init_if_needed hands us a zeroed receipt the first time this funder chips in
+		if contribution.amount == 0 {
+			contribution.item = item.key();
+			contribution.funder = funder.key();
+			require!(item.funders < MAX_FUNDERS, ErrorCode::TooManyFunders);
+			item.funders += 1;
+		}
+
+		invoke(
+			&transfer(funder.key, item.to_account_info().key, amount),
+			&[
+				funder.to_account_info(),
+				item.to_account_info(),
+				ctx.accounts.system_program.to_account_info(),
+			],
+		)?;
+
+		contribution.amount += amount;
+		item.funded_amount += amount;
+		item.bounty_amount += amount;
+
+		emit!(ItemFunded {
+			list: ctx.accounts.list.key(),
+			item: item.key(),
+			funder: funder.key(),
+			amount,
+			bounty: item.bounty_amount,
+		});
+
+		Ok(())
+	}
+
+	pub fn finish<'info>(ctx: Context<'_, '_, '_, 'info, Finish<'info>>, _list_name: String) -> Result<()> {
+		check_not_paused(&ctx.accounts.config, Operation::Other)?;
+		let item = &mut ctx.accounts.item;
+		let list = ctx.accounts.list.key();
+		let list_owner = ctx.accounts.list_owner.key;
 		let user = ctx.accounts.user.to_account_info().key;
 
-		require!(list.lines.contains(item.to_account_info().key), ErrorCode::ItemNotFound);
+		require!(!item.disputed, ErrorCode::ItemDisputed);
 
 		let is_item_creator = &item.creator == user;
-		let is_list_owner = &list.list_owner == user;
+		let is_list_owner = list_owner == user;
+		// Moderators mark the list owner's side of an item
+		let is_moderator = !is_item_creator && !is_list_owner && {
+			let moderator = load_moderator(&ctx.accounts.moderator)?.ok_or(ErrorCode::FinishPermissions)?;
+			require!(moderator.can_finish, ErrorCode::ModeratorCannotFinish);
+			true
+		};
 
-		require!(is_item_creator || is_list_owner, ErrorCode::FinishPermissions);
+		require!(is_item_creator || is_list_owner || is_moderator, ErrorCode::FinishPermissions);
 
 		if is_item_creator {
 			item.creator_finished = true;
+			emit!(ItemFinishMarked {
+				list,
+				item: item.key(),
+				marked_by: *user,
+				side: FinishSide::Creator,
+			});
 		}
 
-		if is_list_owner {
+		if is_list_owner || is_moderator {
+			// The review window runs from the first time the owner side marked the item
+			if !item.list_owner_finished {
+				item.owner_finished_at = Some(Clock::get()?.unix_timestamp);
+			}
 			item.list_owner_finished = true;
+			emit!(ItemFinishMarked {
+				list,
+				item: item.key(),
+				marked_by: *user,
+				side: FinishSide::ListOwner,
+			});
 		}
 
 		if item.creator_finished && item.list_owner_finished {
-			let item_key = item.to_account_info().key;
-			list.lines.retain(|key| key != item_key);
-			item.close(ctx.accounts.list_owner.to_account_info())?;
+			check_not_paused(&ctx.accounts.config, Operation::Payout)?;
+			remove_line(&ctx.accounts.list, &ctx.accounts.page, item, &ctx.accounts.moved_item)?;
+			let fee = collect_fee(item, item.bounty_amount, &ctx.accounts.config, &ctx.accounts.treasury)?;
+
+			emit!(ItemPaid {
+				list,
+				item: item.key(),
+				recipient: *list_owner,
+				bounty_mint: item.bounty_mint,
+				amount: item.bounty_amount - fee,
+			});
+
+			// The whole pool goes to the list owner, funders only get the rent of their receipts back
+			settle_contributions(item, 0, ctx.remaining_accounts)?;
+			release_bounty(item, &ctx.accounts.list_owner.to_account_info(), ctx.remaining_accounts)?;
+		}
+
+		Ok(())
+	}
+
+	/// Clears the finish mark of the caller's side while the item is still open.
+	pub fn unfinish(ctx: Context<Unfinish>, _list_name: String) -> Result<()> {
+		check_not_paused(&ctx.accounts.config, Operation::Other)?;
+		let item = &mut ctx.accounts.item;
+		let list = ctx.accounts.list.key();
+		let user = ctx.accounts.user.to_account_info().key;
+
+		require!(!item.disputed, ErrorCode::ItemDisputed);
+
+		let is_item_creator = &item.creator == user;
+		let is_list_owner = ctx.accounts.list_owner.key == user;
+		// Moderators that may finish items may also take back the list owner's mark
+		let is_moderator = !is_item_creator && !is_list_owner && {
+			let moderator = load_moderator(&ctx.accounts.moderator)?.ok_or(ErrorCode::FinishPermissions)?;
+			require!(moderator.can_finish, ErrorCode::ModeratorCannotFinish);
+			true
+		};
+
+		let clears_creator = is_item_creator && item.creator_finished;
+		let clears_owner = (is_list_owner || is_moderator) && item.list_owner_finished;
+		require!(clears_creator || clears_owner, ErrorCode::NotMarkedFinished);
+
+		if clears_creator {
+			item.creator_finished = false;
+			emit!(ItemFinishRevoked {
+				list,
+				item: item.key(),
+				revoked_by: *user,
+				side: FinishSide::Creator,
+			});
+		}
+
+		if clears_owner {
+			item.list_owner_finished = false;
+			item.owner_finished_at = None;
+			emit!(ItemFinishRevoked {
+				list,
+				item: item.key(),
+				revoked_by: *user,
+				side: FinishSide::ListOwner,
+			});
+		}
+
+		Ok(())
+	}
+
+	/// Pays out an item the list owner marked finished once the list's review window passed
+	/// without the creator finishing, disputing or cancelling it.
+	pub fn claim_after_timeout<'info>(
+		ctx: Context<'_, '_, '_, 'info, ClaimAfterTimeout<'info>>,
+		_list_name: String,
+	) -> Result<()> {
+		check_not_paused(&ctx.accounts.config, Operation::Payout)?;
+		let item = &mut ctx.accounts.item;
+
+		remove_line(&ctx.accounts.list, &ctx.accounts.page, item, &ctx.accounts.moved_item)?;
+		require!(!item.disputed, ErrorCode::ItemDisputed);
+
+		let review_window = ctx.accounts.list.load()?.review_window;
+		require!(review_window > 0, ErrorCode::NoReviewWindow);
+		let finished_at = item.owner_finished_at.ok_or(ErrorCode::OwnerNotFinished)?;
+		require!(
+			Clock::get()?.unix_timestamp >= finished_at.saturating_add(review_window),
+			ErrorCode::ReviewWindowOpen
+		);
+		let fee = collect_fee(item, item.bounty_amount, &ctx.accounts.config, &ctx.accounts.treasury)?;
+
+		emit!(ItemPaid {
+			list: ctx.accounts.list.key(),
+			item: item.key(),
+			recipient: ctx.accounts.list_owner.key(),
+			bounty_mint: item.bounty_mint,
+			amount: item.bounty_amount - fee,
+		});
+
+		settle_contributions(item, 0, ctx.remaining_accounts)?;
+		release_bounty(item, &ctx.accounts.list_owner.to_account_info(), ctx.remaining_accounts)
+	}
+
+	/// Closes an owner's list and its pages. `remaining_accounts` start with a `[page, page_payer]` pair for
+	/// every page still open in page order, the rent of each page returns to whoever opened it. Remaining items are
+	/// cancelled when passed as `[item, item_creator]` pairs after them, which only works for plain lamport bounties.
+	pub fn close_list<'info>(ctx: Context<'_, '_, '_, 'info, CloseList<'info>>, _list_name: String) -> Result<()> {
+		check_not_paused(&ctx.accounts.config, Operation::Refund)?;
+		let page_count = ctx.accounts.list.load()?.page_count as usize;
+		// Moderator PDAs are derived from the list address and would carry over to a list created again under it
+		require!(ctx.accounts.list.load()?.moderator_count == 0, ErrorCode::ModeratorsLeft);
+		require!(ctx.remaining_accounts.len() >= 2 * page_count, ErrorCode::MissingPages);
+		let (page_accounts, item_accounts) = ctx.remaining_accounts.split_at(2 * page_count);
+
+		let mut pages = Vec::with_capacity(page_count);
+		for (page_no, pair) in page_accounts.chunks(2).enumerate() {
+			let page: AccountLoader<ListPage> = AccountLoader::try_from(&pair[0])?;
+			{
+				let page = page.load()?;
+				require!(
+					page.list == ctx.accounts.list.key() && page.page_no as usize == page_no,
+					ErrorCode::MissingPages
+				);
+				require!(&page.payer == pair[1].key, ErrorCode::WrongPagePayer);
+			}
+			pages.push(page);
+		}
+
+		for pair in item_accounts.chunks(2) {
+			let (item_info, item_creator) = match pair {
+				[item_info, item_creator] => (item_info, item_creator),
+				_ => return err!(ErrorCode::ListNotEmpty),
+			};
+
+			// Closed items fail to deserialize, so an item cannot be refunded twice
+			let item: Account<ListItem> = Account::try_from(item_info)?;
+			require!(item.list == ctx.accounts.list.key(), ErrorCode::ItemNotFound);
+			{
+				let mut page = pages.get(item.page_no as usize).ok_or(ErrorCode::WrongPage)?.load_mut()?;
+				let slot = item.slot as usize;
+				require!(page.items().get(slot) == Some(item_info.key), ErrorCode::WrongPage);
+				// The pages are closed with the list, the slot only has to stop matching the item
+				page.lines[slot] = Pubkey::default();
+			}
+			ctx.accounts.list.load_mut()?.len -= 1;
+			require!(&item.creator == item_creator.key, ErrorCode::WrongItemCreator);
+			require!(
+				item.bounty_mint.is_none() && item.funders == 0 && !item.disputed,
+				ErrorCode::ItemNeedsCancel
+			);
+
+			emit!(ItemCancelled {
+				list: ctx.accounts.list.key(),
+				item: item.key(),
+				cancelled_by: ctx.accounts.list_owner.key(),
+				refund_recipient: item.creator,
+			});
+
+			item.close(item_creator.clone())?;
+		}
+
+		require!(ctx.accounts.list.load()?.len == 0, ErrorCode::ListNotEmpty);
+
+		for (page, pair) in pages.iter().zip(page_accounts.chunks(2)) {
+			page.close(pair[1].clone())?;
+		}
+
+		emit!(ListClosed { list: ctx.accounts.list.key() });
+
+		Ok(())
+	}
+
+	/// Closes the last page of a list once it holds no items, its rent returns to whoever opened it.
+	/// Lists with more pages than fit into one transaction close their emptied pages this way before `close_list`.
+	pub fn close_page(ctx: Context<ClosePage>, _list_name: String) -> Result<()> {
+		check_not_paused(&ctx.accounts.config, Operation::Refund)?;
+		let mut list = ctx.accounts.list.load_mut()?;
+		let page = ctx.accounts.page.load()?;
+
+		require!(page.page_no + 1 == list.page_count, ErrorCode::NotLastPage);
+		require!(page.len == 0, ErrorCode::PageNotEmpty);
+		list.page_count -= 1;
+
+		emit!(PageClosed {
+			list: ctx.accounts.list.key(),
+			page_no: page.page_no,
+		});
+
+		Ok(())
+	}
+
+	/// Returns the bounty of an item written by an earlier version, before lists kept their items on pages,
+	/// to its creator and takes it off its legacy list. Anyone may reclaim an item, the bounty always goes back to its creator.
+	pub fn reclaim_legacy_item(ctx: Context<ReclaimLegacyItem>) -> Result<()> {
+		check_not_paused(&ctx.accounts.config, Operation::Refund)?;
+		let list_info = ctx.accounts.list.to_account_info();
+		let item_info = ctx.accounts.item.to_account_info();
+
+		let item = LegacyListItem::load(&item_info)?;
+		require!(&item.creator == ctx.accounts.item_creator.key, ErrorCode::WrongItemCreator);
+
+		// Legacy lists only ever held legacy items, being on one tells them apart from current items
+		let mut list = LegacyTodoList::load(&list_info)?;
+		let position = list.lines.iter().position(|key| key == item_info.key).ok_or(ErrorCode::ItemNotFound)?;
+		list.lines.remove(position);
+		list.store(&list_info)?;
+
+		emit!(LegacyItemReclaimed {
+			list: list_info.key(),
+			item: item_info.key(),
+			refund_recipient: item.creator,
+		});
+
+		close_legacy_account(&item_info, &ctx.accounts.item_creator)
+	}
+
+	/// Closes a list written by an earlier version once `reclaim_legacy_item` emptied it, its rent returns to the owner.
+	/// The owner can then create the list again under the same address with the truncated seed scheme.
+	pub fn close_legacy_list(ctx: Context<CloseLegacyList>) -> Result<()> {
+		check_not_paused(&ctx.accounts.config, Operation::Refund)?;
+		let list_info = ctx.accounts.list.to_account_info();
+
+		let list = LegacyTodoList::load(&list_info)?;
+		require!(&list.list_owner == ctx.accounts.list_owner.key, ErrorCode::WrongListOwner);
+		require!(list.lines.is_empty(), ErrorCode::ListNotEmpty);
+
+		emit!(ListClosed { list: list_info.key() });
+
+		close_legacy_account(&list_info, &ctx.accounts.list_owner.to_account_info())
+	}
+
+	/// Changes the most items the list may hold at once, `None` lifts the cap.
+	pub fn resize_list(ctx: Context<ResizeList>, _list_name: String, capacity: Option<u32>) -> Result<()> {
+		check_not_paused(&ctx.accounts.config, Operation::Other)?;
+		let mut list = ctx.accounts.list.load_mut()?;
+
+		if let Some(capacity) = capacity {
+			require!(capacity > 0 && list.len <= capacity, ErrorCode::CapacityTooSmall);
+		}
+		list.capacity = capacity.unwrap_or_default();
+
+		emit!(ListResized {
+			list: ctx.accounts.list.key(),
+			capacity,
+		});
+
+		Ok(())
+	}
+
+	/// Nominates `new_owner` as the next list owner, `None` withdraws a pending nomination.
+	pub fn propose_owner(ctx: Context<ProposeOwner>, _list_name: String, new_owner: Option<Pubkey>) -> Result<()> {
+		check_not_paused(&ctx.accounts.config, Operation::Other)?;
+		ctx.accounts.list.load_mut()?.pending_owner = new_owner.unwrap_or_default();
+
+		emit!(OwnerProposed {
+			list: ctx.accounts.list.key(),
+			pending_owner: new_owner,
+		});
+
+		Ok(())
+	}
+
+	pub fn accept_owner(ctx: Context<AcceptOwner>, _list_name: String) -> Result<()> {
+		check_not_paused(&ctx.accounts.config, Operation::Other)?;
+		let mut list = ctx.accounts.list.load_mut()?;
+
+		emit!(OwnerAccepted {
+			list: ctx.accounts.list.key(),
+			previous_owner: list.list_owner,
+			new_owner: ctx.accounts.new_owner.key(),
+		});
+
+		list.list_owner = *ctx.accounts.new_owner.key;
+		list.pending_owner = Pubkey::default();
+		Ok(())
+	}
+
+	/// Grants `moderator_key` the given roles on the list, replacing any roles they had.
+	pub fn set_moderator(
+		ctx: Context<SetModerator>,
+		_list_name: String,
+		moderator_key: Pubkey,
+		can_cancel: bool,
+		can_finish: bool,
+	) -> Result<()> {
+		check_not_paused(&ctx.accounts.config, Operation::Other)?;
+		let roles = &mut ctx.accounts.moderator;
+		// Roles of a new moderator are still zeroed, replacing the roles of a moderator keeps the count
+		if roles.list == Pubkey::default() {
+			ctx.accounts.list.load_mut()?.moderator_count += 1;
 		}
+		roles.list = ctx.accounts.list.key();
+		roles.moderator = moderator_key;
+		roles.can_cancel = can_cancel;
+		roles.can_finish = can_finish;
+
+		emit!(ModeratorSet {
+			list: roles.list,
+			moderator: moderator_key,
+			can_cancel,
+			can_finish,
+		});
+
+		Ok(())
+	}
+
+	pub fn remove_moderator(ctx: Context<RemoveModerator>, _list_name: String, moderator_key: Pubkey) -> Result<()> {
+		check_not_paused(&ctx.accounts.config, Operation::Other)?;
+		ctx.accounts.list.load_mut()?.moderator_count -= 1;
+		emit!(ModeratorRemoved {
+			list: ctx.accounts.list.key(),
+			moderator: moderator_key,
+		});
+
+		Ok(())
+	}
+
+	pub fn open_dispute(ctx: Context<OpenDispute>, _list_name: String) -> Result<()> {
+		check_not_paused(&ctx.accounts.config, Operation::Other)?;
+		let item = &mut ctx.accounts.item;
+		let user = ctx.accounts.user.key;
+
+		require!(ctx.accounts.list.load()?.arbiter != Pubkey::default(), ErrorCode::NoArbiter);
+		require!(
+			ctx.accounts.list_owner.key == user || &item.creator == user,
+			ErrorCode::DisputePermissions
+		);
+		require!(!item.disputed, ErrorCode::ItemDisputed);
+
+		item.disputed = true;
+
+		emit!(DisputeOpened {
+			list: ctx.accounts.list.key(),
+			item: item.key(),
+			opened_by: *user,
+		});
+
+		Ok(())
+	}
+
+	/// Settles a disputed item, paying `owner_share_bps` basis points of the bounty to the list owner
+	/// and refunding the rest to the item creator.
+	pub fn resolve_dispute<'info>(
+		ctx: Context<'_, '_, '_, 'info, ResolveDispute<'info>>,
+		_list_name: String,
+		owner_share_bps: u16,
+	) -> Result<()> {
+		check_not_paused(&ctx.accounts.config, Operation::Payout)?;
+		let list = ctx.accounts.list.key();
+		let item = &mut ctx.accounts.item;
+
+		remove_line(&ctx.accounts.list, &ctx.accounts.page, item, &ctx.accounts.moved_item)?;
+		require!(item.disputed, ErrorCode::ItemNotDisputed);
+		require!(owner_share_bps <= MAX_BPS, ErrorCode::InvalidShare);
+
+		let owner_amount = bps_share(item.bounty_amount, owner_share_bps);
+		// The owner's share is a payout like any other, so it pays the fee recorded on the item
+		let fee = collect_fee(item, owner_amount, &ctx.accounts.config, &ctx.accounts.treasury)?;
+		emit!(DisputeResolved {
+			list,
+			item: item.key(),
+			owner_share_bps,
+		});
+		emit!(ItemPaid {
+			list,
+			item: item.key(),
+			recipient: ctx.accounts.list_owner.key(),
+			bounty_mint: item.bounty_mint,
+			amount: owner_amount - fee,
+		});
+
+		let refund_total = item.bounty_amount - owner_amount;
+		settle_contributions(item, refund_total, ctx.remaining_accounts)?;
+		split_bounty(
+			item,
+			&ctx.accounts.list_owner.to_account_info(),
+			&ctx.accounts.item_creator.to_account_info(),
+			owner_amount - fee,
+			ctx.remaining_accounts,
+		)
+	}
+
+	/// Creates the program config with the upgrade authority of the program as admin,
+	/// until then items are paid out without fee.
+	pub fn init_config(ctx: Context<InitConfig>, fee_bps: u16, treasury: Pubkey) -> Result<()> {
+		let config = &mut ctx.accounts.config;
+		config.admin = ctx.accounts.admin.key();
+		config.set_fee(fee_bps, treasury)
+	}
+
+	/// Changes the fee and the treasury receiving it.
+	pub fn update_config(ctx: Context<UpdateConfig>, fee_bps: u16, treasury: Pubkey) -> Result<()> {
+		ctx.accounts.config.set_fee(fee_bps, treasury)
+	}
+
+	/// Stops the whole program, or only new deposits or payouts, until the admin lifts the pause again.
+	/// With `refunds_open` items can still be cancelled, expired and refunded by closing their list while paused.
+	pub fn set_pause(
+		ctx: Context<UpdateConfig>,
+		paused: bool,
+		deposits_paused: bool,
+		payouts_paused: bool,
+		refunds_open: bool,
+	) -> Result<()> {
+		let config = &mut ctx.accounts.config;
+		config.paused = paused;
+		config.deposits_paused = deposits_paused;
+		config.payouts_paused = payouts_paused;
+		config.refunds_open = refunds_open;
+
+		emit!(PauseUpdated {
+			paused,
+			deposits_paused,
+			payouts_paused,
+			refunds_open,
+		});
 
 		Ok(())
 	}
 }
 
-fn name_seed(name: &str) -> &[u8] {
+/// Longest item description URI, enough for Arweave, IPFS and most HTTPS links
+const MAX_URI_LEN: usize = 200;
+
+/// Most funders an item takes. Settling an item needs a `[contribution, funder]` pair for every funder
+/// next to the accounts of the instruction, which has to fit into a single transaction.
+pub const MAX_FUNDERS: u16 = 8;
+
+/// Basis points making up a whole bounty
+const MAX_BPS: u16 = 10_000;
+
+/// Highest protocol fee the admin may set
+const MAX_FEE_BPS: u16 = 1_000;
+
+fn bps_share(amount: u64, bps: u16) -> u64 {
+	(amount as u128 * bps as u128 / MAX_BPS as u128) as u64
+}
+
+/// Longest list name, the name is stored in full on the list
+const MAX_LIST_NAME_LEN: usize = 64;
+
+/// Longest seed Solana accepts, legacy list names are truncated to it
+const MAX_SEED_LEN: usize = 32;
+
+/// Legacy seed of a list name, names are truncated to the 32 byte seed limit
+pub fn name_seed(name: &str) -> &[u8] {
 	let b = name.as_bytes();
-	if b.len() > 32 {
-		&b[0..32]
+	if b.len() > MAX_SEED_LEN {
+		&b[0..MAX_SEED_LEN]
 	} else {
 		b
 	}
 }
 
+/// Seed a list name contributes to the list address under `scheme`
+pub fn list_seed(name: &str, scheme: SeedScheme) -> Vec<u8> {
+	match scheme {
+		SeedScheme::Truncated => name_seed(name).to_vec(),
+		SeedScheme::Hashed => hash(name.as_bytes()).to_bytes().to_vec(),
+	}
+}
+
+fn check_list_name(name: &str, scheme: SeedScheme) -> Result<()> {
+	require!(!name.is_empty(), ErrorCode::ListNameEmpty);
+	require!(name.len() <= MAX_LIST_NAME_LEN, ErrorCode::ListNameTooLong);
+	// A seed cut inside a character is not valid UTF-8, clients truncating by characters would derive another address
+	if scheme == SeedScheme::Truncated {
+		require!(name.is_char_boundary(MAX_SEED_LEN.min(name.len())), ErrorCode::ListNameSplitsCharacter);
+	}
+	Ok(())
+}
+
 #[derive(Accounts)]
-#[instruction(name: String, capacity: u16)]
+#[instruction(name: String, capacity: Option<u32>, arbiter: Option<Pubkey>, review_window: Option<i64>, seed_scheme: SeedScheme)]
 pub struct NewList<'info> {
 	#[account(init,
         payer=user,
-        space=TodoList::space(&name, capacity),
+        space=ListHeader::space(),
         seeds=[
             b"todolist",
             user.key().as_ref(),
-            name_seed(&name)
+            list_seed(&name, seed_scheme).as_ref()
         ],
         bump)]
-	pub list: Account<'info, TodoList>,
+	pub list: AccountLoader<'info, ListHeader>,
 	pub system_program: Program<'info, System>,
 	#[account(mut)]
 	pub user: Signer<'info>,
+	/// CHECK: config PDA, it only holds pause flags once the config was initialized
+	#[account(seeds=[b"config"], bump)]
+	pub config: AccountInfo<'info>,
 }
 
 #[derive(Accounts)]
-#[instruction(list_name: String, item_name: String, bounty: u64)]
+#[instruction(list_name: String, item_name: String, bounty: u64, deadline: Option<i64>, uri: Option<String>, content_hash: [u8; 32], page_no: u32)]
 pub struct Add<'info> {
-	#[account(mut, has_one=list_owner @ ErrorCode::WrongListOwner, seeds=[b"todolist", list_owner.to_account_info().key.as_ref(), name_seed(&list_name)], bump)]
-	pub list: Account<'info, TodoList>,
+	#[account(mut, has_one=list_owner @ ErrorCode::WrongListOwner, seeds=[b"todolist", list.load()?.seed_owner.as_ref(), list_seed(&list_name, list.load()?.seed_scheme()).as_ref()], bump = list.load()?.bump)]
+	pub list: AccountLoader<'info, ListHeader>,
 	/// CHECK:
 	pub list_owner: AccountInfo<'info>,
+	#[account(init_if_needed, payer=user, space=ListPage::space(), seeds=[b"page", list.key().as_ref(), &page_no.to_le_bytes()], bump)]
+	pub page: AccountLoader<'info, ListPage>,
 	// 8 byte discriminator,
-	#[account(init, payer=user, space=ListItem::space(&item_name))]
+	#[account(init, payer=user, space=ListItem::space(&item_name, &uri), seeds=[b"item", list.key().as_ref(), &list.load()?.item_count.to_le_bytes()], bump)]
+	pub item: Account<'info, ListItem>,
+	pub system_program: Program<'info, System>,
+	#[account(mut)]
+	pub user: Signer<'info>,
+	/// CHECK: config PDA, it only holds pause flags once the config was initialized
+	#[account(seeds=[b"config"], bump)]
+	pub config: AccountInfo<'info>,
+}
+
+/// Closes an account of an earlier version, which is not wrapped in an `Account` that could close itself.
+/// The zeroed data no longer parses, so the account cannot be used again within the transaction.
+fn close_legacy_account(info: &AccountInfo, destination: &AccountInfo) -> Result<()> {
+	**destination.try_borrow_mut_lamports()? += info.lamports();
+	**info.try_borrow_mut_lamports()? = 0;
+	info.try_borrow_mut_data()?.fill(0);
+	Ok(())
+}
+
+/// Loads the roles of a moderator PDA, `None` when the account was never created.
+fn load_moderator<'info>(info: &AccountInfo<'info>) -> Result<Option<Account<'info, Moderator>>> {
+	if info.owner != &crate::ID {
+		return Ok(None);
+	}
+	Ok(Some(Account::try_from(info)?))
+}
+
+/// Loads the program config, `None` while it was never initialized.
+fn load_config<'info>(info: &AccountInfo<'info>) -> Result<Option<Account<'info, Config>>> {
+	if info.owner != &crate::ID {
+		return Ok(None);
+	}
+	Ok(Some(Account::try_from(info)?))
+}
+
+/// Fails while the config pauses `operation`, deployments without config are never paused.
+fn check_not_paused(config: &AccountInfo, operation: Operation) -> Result<()> {
+	if let Some(config) = load_config(config)? {
+		require!(!config.pauses(operation), ErrorCode::ProgramPaused);
+	}
+	Ok(())
+}
+
+/// Moves the fee share recorded on `item` of the `amount` paid to the list owner from its lamport bounty
+/// to the treasury of the config, returning the fee. Items added without fee, which includes token bounties, pay none.
+fn collect_fee<'info>(
+	item: &Account<'info, ListItem>,
+	amount: u64,
+	config: &AccountInfo<'info>,
+	treasury: &AccountInfo<'info>,
+) -> Result<u64> {
+	let config = match load_config(config)? {
+		Some(config) if item.fee_bps > 0 => config,
+		_ => return Ok(0),
+	};
+	require!(treasury.key == &config.treasury, ErrorCode::WrongTreasury);
+
+	let fee = bps_share(amount, item.fee_bps);
+	let item_info = item.to_account_info();
+	**item_info.try_borrow_mut_lamports()? -= fee;
+	**treasury.try_borrow_mut_lamports()? += fee;
+
+	emit!(FeeCollected {
+		item: item.key(),
+		treasury: config.treasury,
+		amount: fee,
+	});
+
+	Ok(fee)
+}
+
+/// Stores `item` in the next free slot of `page`, opening the page if it is the next page of `list`,
+/// and records the index, page and slot on the item.
+fn push_line(
+	list: &AccountLoader<ListHeader>,
+	page: &AccountLoader<ListPage>,
+	page_no: u32,
+	payer: Pubkey,
+	item: &mut Account<ListItem>,
+) -> Result<()> {
+	let mut list_data = list.load_mut()?;
+	require!(
+		list_data.capacity == 0 || list_data.len < list_data.capacity,
+		ErrorCode::ListFull
+	);
+	require!(page_no <= list_data.page_count, ErrorCode::WrongPage);
+
+	let mut page_data = if page_no == list_data.page_count {
+		let mut page_data = page.load_init()?;
+		page_data.list = list.key();
+		page_data.payer = payer;
+		page_data.page_no = page_no;
+		list_data.page_count += 1;
+		page_data
+	} else {
+		page.load_mut()?
+	};
+
+	let len = page_data.len as usize;
+	require!(len < PAGE_SIZE, ErrorCode::PageFull);
+	page_data.lines[len] = item.key();
+	page_data.len += 1;
+	list_data.len += 1;
+
+	item.list = list.key();
+	item.page_no = page_no;
+	item.slot = len as u32;
+	item.index = list_data.item_count;
+	list_data.item_count += 1;
+	Ok(())
+}
+
+/// Removes `item` from its slot on `page`, moving the last item of the page into the freed slot.
+/// `moved_item` has to be that last item unless `item` is the last one itself, its slot is updated.
+fn remove_line(
+	list: &AccountLoader<ListHeader>,
+	page: &AccountLoader<ListPage>,
+	item: &Account<ListItem>,
+	moved_item: &AccountInfo,
+) -> Result<()> {
+	let mut page = page.load_mut()?;
+
+	let slot = item.slot as usize;
+	require!(page.items().get(slot) == Some(&item.key()), ErrorCode::WrongPage);
+
+	let last = page.len as usize - 1;
+	if slot != last {
+		require!(moved_item.key == &page.lines[last], ErrorCode::WrongMovedItem);
+		let mut moved: Account<ListItem> = Account::try_from(moved_item)?;
+		moved.slot = slot as u32;
+		moved.exit(&crate::ID)?;
+		page.lines[slot] = page.lines[last];
+	}
+	page.lines[last] = Pubkey::default();
+	page.len -= 1;
+
+	list.load_mut()?.len -= 1;
+	Ok(())
+}
+
+fn check_uri(uri: &Option<String>) -> Result<()> {
+	if let Some(uri) = uri {
+		require!(!uri.is_empty() && uri.len() <= MAX_URI_LEN, ErrorCode::InvalidUri);
+	}
+	Ok(())
+}
+
+fn check_deadline(deadline: Option<i64>) -> Result<()> {
+	if let Some(deadline) = deadline {
+		require!(deadline > Clock::get()?.unix_timestamp, ErrorCode::DeadlineInPast);
+	}
+	Ok(())
+}
+
+/// Returns the bounty of `item` to its funders and closes the item to `item_creator`,
+/// who gets back their own share of the bounty and the rent.
+fn refund_bounty<'info>(
+	item: &Account<'info, ListItem>,
+	item_creator: &AccountInfo<'info>,
+	remaining_accounts: &[AccountInfo<'info>],
+) -> Result<()> {
+	settle_contributions(item, item.bounty_amount, remaining_accounts)?;
+	release_bounty(item, item_creator, remaining_accounts)
+}
+
+/// Closes every contribution receipt of `item`, refunding each funder their pro-rata share of `refund_total`.
+/// Items with funders expect one `[contribution, funder]` pair per funder at the start of `remaining_accounts`.
+fn settle_contributions<'info>(
+	item: &Account<'info, ListItem>,
+	refund_total: u64,
+	remaining_accounts: &[AccountInfo<'info>],
+) -> Result<()> {
+	let funders = item.funders as usize;
+	require!(remaining_accounts.len() >= 2 * funders, ErrorCode::MissingContributions);
+
+	let item_info = item.to_account_info();
+	for pair in remaining_accounts[..2 * funders].chunks(2) {
+		let (contribution_info, funder) = (&pair[0], &pair[1]);
+
+		// Closed receipts fail to deserialize, so the same funder cannot be refunded twice
+		let contribution: Account<Contribution> = Account::try_from(contribution_info)?;
+		let (contribution_key, _) =
+			Pubkey::find_program_address(&[b"contribution", item.key().as_ref(), funder.key.as_ref()], &crate::ID);
+		require!(contribution_info.key == &contribution_key, ErrorCode::WrongContribution);
+
+		let refund = (refund_total as u128 * contribution.amount as u128 / item.bounty_amount as u128) as u64;
+		**item_info.try_borrow_mut_lamports()? -= refund;
+		**funder.try_borrow_mut_lamports()? += refund;
+
+		contribution.close(funder.clone())?;
+	}
+
+	Ok(())
+}
+
+/// Pays the whole bounty of `item` to `recipient` and closes the item to them.
+/// Token bounties expect the escrow accounts described on [`Escrow`] in `remaining_accounts`,
+/// followed by the recipient's token account.
+fn release_bounty<'info>(
+	item: &Account<'info, ListItem>,
+	recipient: &AccountInfo<'info>,
+	remaining_accounts: &[AccountInfo<'info>],
+) -> Result<()> {
+	if let Some(mint) = item.bounty_mint {
+		let escrow = Escrow::load(&item.key(), &mint, remaining_accounts)?;
+		let destination = escrow.destination(remaining_accounts, 0, recipient.key)?;
+		escrow.transfer(destination, escrow.account.amount)?;
+		escrow.close(recipient)?;
+	}
+
+	item.close(recipient.clone())
+}
+
+/// Pays `owner_amount` of the bounty of `item` to `list_owner` and the rest to `item_creator`,
+/// who also receives the rent when the item is closed. Funders and the fee must have been paid first.
+/// Token bounties expect the escrow accounts described on [`Escrow`] in `remaining_accounts`,
+/// followed by the token accounts of the list owner and the item creator.
+fn split_bounty<'info>(
+	item: &Account<'info, ListItem>,
+	list_owner: &AccountInfo<'info>,
+	item_creator: &AccountInfo<'info>,
+	owner_amount: u64,
+	remaining_accounts: &[AccountInfo<'info>],
+) -> Result<()> {
+	if let Some(mint) = item.bounty_mint {
+		let escrow = Escrow::load(&item.key(), &mint, remaining_accounts)?;
+		let owner_destination = escrow.destination(remaining_accounts, 0, list_owner.key)?;
+		let creator_destination = escrow.destination(remaining_accounts, 1, item_creator.key)?;
+
+		escrow.transfer(owner_destination, owner_amount)?;
+		escrow.transfer(creator_destination, escrow.account.amount - owner_amount)?;
+		escrow.close(item_creator)?;
+	} else {
+		let item_info = item.to_account_info();
+		**item_info.try_borrow_mut_lamports()? -= owner_amount;
+		**list_owner.try_borrow_mut_lamports()? += owner_amount;
+	}
+
+	item.close(item_creator.clone())
+}
+
+/// The SPL token escrow of an item, passed through `remaining_accounts` as
+/// `[escrow, escrow_authority, token_program, ..destination token accounts]`.
+struct Escrow<'info> {
+	account: Account<'info, TokenAccount>,
+	authority: AccountInfo<'info>,
+	token_program: AccountInfo<'info>,
+	item: Pubkey,
+	mint: Pubkey,
+	bump: u8,
+}
+
+impl<'info> Escrow<'info> {
+	const ACCOUNTS: usize = 3;
+
+	fn load(item: &Pubkey, mint: &Pubkey, accounts: &[AccountInfo<'info>]) -> Result<Self> {
+		require!(accounts.len() >= Self::ACCOUNTS, ErrorCode::MissingEscrowAccounts);
+
+		let (escrow_key, _) = Pubkey::find_program_address(&[b"escrow", item.as_ref()], &crate::ID);
+		let (authority_key, bump) = Pubkey::find_program_address(&[b"escrow_authority", item.as_ref()], &crate::ID);
+
+		require!(accounts[0].key == &escrow_key, ErrorCode::WrongEscrow);
+		require!(accounts[1].key == &authority_key, ErrorCode::WrongEscrow);
+		require!(accounts[2].key == &token::ID, ErrorCode::WrongEscrow);
+
+		Ok(Escrow {
+			account: Account::try_from(&accounts[0])?,
+			authority: accounts[1].clone(),
+			token_program: accounts[2].clone(),
+			item: *item,
+			mint: *mint,
+			bump,
+		})
+	}
+
+	/// Returns the `index`th destination token account, checking that it holds the bounty mint for `owner`.
+	fn destination<'a>(&self, accounts: &'a [AccountInfo<'info>], index: usize, owner: &Pubkey) -> Result<&'a AccountInfo<'info>> {
+		let info = accounts
+			.get(Self::ACCOUNTS + index)
+			.ok_or(ErrorCode::MissingEscrowAccounts)?;
+		let destination: Account<TokenAccount> = Account::try_from(info)?;
+
+		require!(destination.mint == self.mint, ErrorCode::WrongTokenAccount);
+		require!(&destination.owner == owner, ErrorCode::WrongTokenAccount);
+
+		Ok(info)
+	}
+
+	fn transfer(&self, to: &AccountInfo<'info>, amount: u64) -> Result<()> {
+		if amount == 0 {
+			return Ok(());
+		}
+
+		token::transfer(
+			CpiContext::new_with_signer(
+				self.token_program.clone(),
+				Transfer {
+					from: self.account.to_account_info(),
+					to: to.clone(),
+					authority: self.authority.clone(),
+				},
+				&[&[b"escrow_authority", self.item.as_ref(), &[self.bump]]],
+			),
+			amount,
+		)
+	}
+
+	/// Closes the escrow token account, sending its rent to `rent_receiver`.
+	fn close(&self, rent_receiver: &AccountInfo<'info>) -> Result<()> {
+		token::close_account(CpiContext::new_with_signer(
+			self.token_program.clone(),
+			CloseAccount {
+				account: self.account.to_account_info(),
+				destination: rent_receiver.clone(),
+				authority: self.authority.clone(),
+			},
+			&[&[b"escrow_authority", self.item.as_ref(), &[self.bump]]],
+		))
+	}
+}
+
+#[derive(Accounts)]
+#[instruction(list_name: String, item_name: String, amount: u64, deadline: Option<i64>, uri: Option<String>, content_hash: [u8; 32], page_no: u32)]
+pub struct AddToken<'info> {
+	#[account(mut, has_one=list_owner @ ErrorCode::WrongListOwner, seeds=[b"todolist", list.load()?.seed_owner.as_ref(), list_seed(&list_name, list.load()?.seed_scheme()).as_ref()], bump = list.load()?.bump)]
+	pub list: AccountLoader<'info, ListHeader>,
+	/// CHECK:
+	pub list_owner: AccountInfo<'info>,
+	#[account(init_if_needed, payer=user, space=ListPage::space(), seeds=[b"page", list.key().as_ref(), &page_no.to_le_bytes()], bump)]
+	pub page: AccountLoader<'info, ListPage>,
+	#[account(init, payer=user, space=ListItem::space(&item_name, &uri), seeds=[b"item", list.key().as_ref(), &list.load()?.item_count.to_le_bytes()], bump)]
 	pub item: Account<'info, ListItem>,
+	pub mint: Account<'info, Mint>,
+	#[account(init, payer=user, seeds=[b"escrow", item.key().as_ref()], bump, token::mint=mint, token::authority=escrow_authority)]
+	pub escrow: Account<'info, TokenAccount>,
+	/// CHECK: PDA that signs for the escrow, it holds no data.
+	#[account(seeds=[b"escrow_authority", item.key().as_ref()], bump)]
+	pub escrow_authority: AccountInfo<'info>,
+	#[account(mut, constraint = user_token.mint == mint.key() @ ErrorCode::WrongTokenAccount)]
+	pub user_token: Account<'info, TokenAccount>,
+	pub token_program: Program<'info, Token>,
 	pub system_program: Program<'info, System>,
+	pub rent: Sysvar<'info, Rent>,
 	#[account(mut)]
 	pub user: Signer<'info>,
+	/// CHECK: config PDA, it only holds pause flags once the config was initialized
+	#[account(seeds=[b"config"], bump)]
+	pub config: AccountInfo<'info>,
 }
 
 #[derive(Accounts)]
 #[instruction(list_name: String)]
 pub struct Cancel<'info> {
-	#[account(mut, has_one=list_owner @ ErrorCode::WrongListOwner, seeds=[b"todolist", list_owner.to_account_info().key.as_ref(), name_seed(&list_name)], bump)]
-	pub list: Account<'info, TodoList>,
+	#[account(mut, has_one=list_owner @ ErrorCode::WrongListOwner, seeds=[b"todolist", list.load()?.seed_owner.as_ref(), list_seed(&list_name, list.load()?.seed_scheme()).as_ref()], bump = list.load()?.bump)]
+	pub list: AccountLoader<'info, ListHeader>,
 	/// CHECK:
 	pub list_owner: AccountInfo<'info>,
+	#[account(mut, has_one=list @ ErrorCode::WrongPage)]
+	pub page: AccountLoader<'info, ListPage>,
+	#[account(mut, has_one=list @ ErrorCode::ItemNotFound)]
+	pub item: Account<'info, ListItem>,
+	/// CHECK: last item on the page, moved into the slot of the removed item. Checked by `remove_line`
 	#[account(mut)]
+	pub moved_item: AccountInfo<'info>,
+	#[account(mut, address=item.creator @ ErrorCode::WrongItemCreator)]
+	/// CHECK:
+	pub item_creator: AccountInfo<'info>,
+	pub user: Signer<'info>,
+	/// CHECK: moderator PDA of the user, it only holds roles if the user is a moderator
+	#[account(seeds=[b"moderator", list.key().as_ref(), user.key().as_ref()], bump)]
+	pub moderator: AccountInfo<'info>,
+	/// CHECK: config PDA, it only holds pause flags once the config was initialized
+	#[account(seeds=[b"config"], bump)]
+	pub config: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(list_name: String)]
+pub struct Expire<'info> {
+	#[account(mut, has_one=list_owner @ ErrorCode::WrongListOwner, seeds=[b"todolist", list.load()?.seed_owner.as_ref(), list_seed(&list_name, list.load()?.seed_scheme()).as_ref()], bump = list.load()?.bump)]
+	pub list: AccountLoader<'info, ListHeader>,
+	/// CHECK:
+	pub list_owner: AccountInfo<'info>,
+	#[account(mut, has_one=list @ ErrorCode::WrongPage)]
+	pub page: AccountLoader<'info, ListPage>,
+	#[account(mut, has_one=list @ ErrorCode::ItemNotFound)]
 	pub item: Account<'info, ListItem>,
+	/// CHECK: last item on the page, moved into the slot of the removed item. Checked by `remove_line`
+	#[account(mut)]
+	pub moved_item: AccountInfo<'info>,
 	#[account(mut, address=item.creator @ ErrorCode::WrongItemCreator)]
 	/// CHECK:
 	pub item_creator: AccountInfo<'info>,
+	/// CHECK: config PDA, it only holds pause flags once the config was initialized
+	#[account(seeds=[b"config"], bump)]
+	pub config: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(list_name: String)]
+pub struct CloseList<'info> {
+	#[account(mut, has_one=list_owner @ ErrorCode::WrongListOwner, close=list_owner, seeds=[b"todolist", list.load()?.seed_owner.as_ref(), list_seed(&list_name, list.load()?.seed_scheme()).as_ref()], bump = list.load()?.bump)]
+	pub list: AccountLoader<'info, ListHeader>,
+	#[account(mut)]
+	pub list_owner: Signer<'info>,
+	/// CHECK: config PDA, it only holds pause flags once the config was initialized
+	#[account(seeds=[b"config"], bump)]
+	pub config: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(list_name: String)]
+pub struct ClosePage<'info> {
+	#[account(mut, seeds=[b"todolist", list.load()?.seed_owner.as_ref(), list_seed(&list_name, list.load()?.seed_scheme()).as_ref()], bump = list.load()?.bump)]
+	pub list: AccountLoader<'info, ListHeader>,
+	#[account(mut, has_one=list @ ErrorCode::WrongPage, has_one=payer @ ErrorCode::WrongPagePayer, close=payer)]
+	pub page: AccountLoader<'info, ListPage>,
+	/// CHECK: opened the page and receives its rent. Checked by has_one on the page
+	#[account(mut)]
+	pub payer: AccountInfo<'info>,
+	/// CHECK: config PDA, it only holds pause flags once the config was initialized
+	#[account(seeds=[b"config"], bump)]
+	pub config: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ReclaimLegacyItem<'info> {
+	/// CHECK: list written by an earlier version. Parsed as a `LegacyTodoList` by the instruction
+	#[account(mut, owner=crate::ID)]
+	pub list: AccountInfo<'info>,
+	/// CHECK: item written by an earlier version. Parsed as a `LegacyListItem` and looked up on the list by the instruction
+	#[account(mut, owner=crate::ID)]
+	pub item: AccountInfo<'info>,
+	/// CHECK: receives the bounty. Checked against the creator in the item
+	#[account(mut)]
+	pub item_creator: AccountInfo<'info>,
+	/// CHECK: config PDA, it only holds pause flags once the config was initialized
+	#[account(seeds=[b"config"], bump)]
+	pub config: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseLegacyList<'info> {
+	/// CHECK: list written by an earlier version. Parsed as a `LegacyTodoList` by the instruction
+	#[account(mut, owner=crate::ID)]
+	pub list: AccountInfo<'info>,
+	#[account(mut)]
+	pub list_owner: Signer<'info>,
+	/// CHECK: config PDA, it only holds pause flags once the config was initialized
+	#[account(seeds=[b"config"], bump)]
+	pub config: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(list_name: String)]
+pub struct ResizeList<'info> {
+	#[account(mut, has_one=list_owner @ ErrorCode::WrongListOwner, seeds=[b"todolist", list.load()?.seed_owner.as_ref(), list_seed(&list_name, list.load()?.seed_scheme()).as_ref()], bump = list.load()?.bump)]
+	pub list: AccountLoader<'info, ListHeader>,
+	#[account(mut)]
+	pub list_owner: Signer<'info>,
+	/// CHECK: config PDA, it only holds pause flags once the config was initialized
+	#[account(seeds=[b"config"], bump)]
+	pub config: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(list_name: String)]
+pub struct ProposeOwner<'info> {
+	#[account(mut, has_one=list_owner @ ErrorCode::WrongListOwner, seeds=[b"todolist", list.load()?.seed_owner.as_ref(), list_seed(&list_name, list.load()?.seed_scheme()).as_ref()], bump = list.load()?.bump)]
+	pub list: AccountLoader<'info, ListHeader>,
+	pub list_owner: Signer<'info>,
+	/// CHECK: config PDA, it only holds pause flags once the config was initialized
+	#[account(seeds=[b"config"], bump)]
+	pub config: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(list_name: String)]
+pub struct AcceptOwner<'info> {
+	#[account(mut, seeds=[b"todolist", list.load()?.seed_owner.as_ref(), list_seed(&list_name, list.load()?.seed_scheme()).as_ref()], bump = list.load()?.bump)]
+	pub list: AccountLoader<'info, ListHeader>,
+	#[account(constraint = list.load()?.pending_owner == new_owner.key() @ ErrorCode::NotPendingOwner)]
+	pub new_owner: Signer<'info>,
+	/// CHECK: config PDA, it only holds pause flags once the config was initialized
+	#[account(seeds=[b"config"], bump)]
+	pub config: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(list_name: String, moderator_key: Pubkey)]
+pub struct SetModerator<'info> {
+	#[account(mut, has_one=list_owner @ ErrorCode::WrongListOwner, seeds=[b"todolist", list.load()?.seed_owner.as_ref(), list_seed(&list_name, list.load()?.seed_scheme()).as_ref()], bump = list.load()?.bump)]
+	pub list: AccountLoader<'info, ListHeader>,
+	#[account(init_if_needed, payer=list_owner, space=Moderator::space(), seeds=[b"moderator", list.key().as_ref(), moderator_key.as_ref()], bump)]
+	pub moderator: Account<'info, Moderator>,
+	pub system_program: Program<'info, System>,
+	#[account(mut)]
+	pub list_owner: Signer<'info>,
+	/// CHECK: config PDA, it only holds pause flags once the config was initialized
+	#[account(seeds=[b"config"], bump)]
+	pub config: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(list_name: String, moderator_key: Pubkey)]
+pub struct RemoveModerator<'info> {
+	#[account(mut, has_one=list_owner @ ErrorCode::WrongListOwner, seeds=[b"todolist", list.load()?.seed_owner.as_ref(), list_seed(&list_name, list.load()?.seed_scheme()).as_ref()], bump = list.load()?.bump)]
+	pub list: AccountLoader<'info, ListHeader>,
+	#[account(mut, close=list_owner, seeds=[b"moderator", list.key().as_ref(), moderator_key.as_ref()], bump)]
+	pub moderator: Account<'info, Moderator>,
+	#[account(mut)]
+	pub list_owner: Signer<'info>,
+	/// CHECK: config PDA, it only holds pause flags once the config was initialized
+	#[account(seeds=[b"config"], bump)]
+	pub config: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(list_name: String)]
+pub struct OpenDispute<'info> {
+	#[account(has_one=list_owner @ ErrorCode::WrongListOwner, seeds=[b"todolist", list.load()?.seed_owner.as_ref(), list_seed(&list_name, list.load()?.seed_scheme()).as_ref()], bump = list.load()?.bump)]
+	pub list: AccountLoader<'info, ListHeader>,
+	/// CHECK:
+	pub list_owner: AccountInfo<'info>,
+	#[account(mut, has_one=list @ ErrorCode::ItemNotFound)]
+	pub item: Account<'info, ListItem>,
 	pub user: Signer<'info>,
+	/// CHECK: config PDA, it only holds pause flags once the config was initialized
+	#[account(seeds=[b"config"], bump)]
+	pub config: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(list_name: String)]
+pub struct ResolveDispute<'info> {
+	#[account(mut, has_one=list_owner @ ErrorCode::WrongListOwner, seeds=[b"todolist", list.load()?.seed_owner.as_ref(), list_seed(&list_name, list.load()?.seed_scheme()).as_ref()], bump = list.load()?.bump)]
+	pub list: AccountLoader<'info, ListHeader>,
+	#[account(mut)]
+	/// CHECK:
+	pub list_owner: AccountInfo<'info>,
+	#[account(mut, has_one=list @ ErrorCode::WrongPage)]
+	pub page: AccountLoader<'info, ListPage>,
+	#[account(mut, has_one=list @ ErrorCode::ItemNotFound)]
+	pub item: Account<'info, ListItem>,
+	/// CHECK: last item on the page, moved into the slot of the removed item. Checked by `remove_line`
+	#[account(mut)]
+	pub moved_item: AccountInfo<'info>,
+	#[account(mut, address=item.creator @ ErrorCode::WrongItemCreator)]
+	/// CHECK:
+	pub item_creator: AccountInfo<'info>,
+	#[account(constraint = list.load()?.arbiter == arbiter.key() @ ErrorCode::NotArbiter)]
+	pub arbiter: Signer<'info>,
+	/// CHECK: config PDA, it only holds a fee and pause flags once the config was initialized
+	#[account(seeds=[b"config"], bump)]
+	pub config: AccountInfo<'info>,
+	/// CHECK: receives the fee, checked against the config by `collect_fee`
+	#[account(mut)]
+	pub treasury: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(list_name: String)]
+pub struct EditItem<'info> {
+	#[account(has_one=list_owner @ ErrorCode::WrongListOwner, seeds=[b"todolist", list.load()?.seed_owner.as_ref(), list_seed(&list_name, list.load()?.seed_scheme()).as_ref()], bump = list.load()?.bump)]
+	pub list: AccountLoader<'info, ListHeader>,
+	/// CHECK:
+	pub list_owner: AccountInfo<'info>,
+	#[account(mut, has_one=list @ ErrorCode::ItemNotFound)]
+	pub item: Account<'info, ListItem>,
+	pub system_program: Program<'info, System>,
+	#[account(mut, address=item.creator @ ErrorCode::EditPermissions)]
+	pub item_creator: Signer<'info>,
+	/// CHECK: config PDA, it only holds pause flags once the config was initialized
+	#[account(seeds=[b"config"], bump)]
+	pub config: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(list_name: String)]
+pub struct Fund<'info> {
+	#[account(has_one=list_owner @ ErrorCode::WrongListOwner, seeds=[b"todolist", list.load()?.seed_owner.as_ref(), list_seed(&list_name, list.load()?.seed_scheme()).as_ref()], bump = list.load()?.bump)]
+	pub list: AccountLoader<'info, ListHeader>,
+	/// CHECK:
+	pub list_owner: AccountInfo<'info>,
+	#[account(mut, has_one=list @ ErrorCode::ItemNotFound)]
+	pub item: Account<'info, ListItem>,
+	#[account(init_if_needed, payer=funder, space=Contribution::space(), seeds=[b"contribution", item.key().as_ref(), funder.key().as_ref()], bump)]
+	pub contribution: Account<'info, Contribution>,
+	pub system_program: Program<'info, System>,
+	#[account(mut)]
+	pub funder: Signer<'info>,
+	/// CHECK: config PDA, it only holds pause flags once the config was initialized
+	#[account(seeds=[b"config"], bump)]
+	pub config: AccountInfo<'info>,
 }
 
 #[derive(Accounts)]
 #[instruction(list_name: String)]
 pub struct Finish<'info> {
-	#[account(mut, has_one=list_owner @ ErrorCode::WrongListOwner, seeds=[b"todolist", list_owner.to_account_info().key.as_ref(), name_seed(&list_name)], bump)]
-	pub list: Account<'info, TodoList>,
+	#[account(mut, has_one=list_owner @ ErrorCode::WrongListOwner, seeds=[b"todolist", list.load()?.seed_owner.as_ref(), list_seed(&list_name, list.load()?.seed_scheme()).as_ref()], bump = list.load()?.bump)]
+	pub list: AccountLoader<'info, ListHeader>,
 	#[account(mut)]
 	/// CHECK:
 	pub list_owner: AccountInfo<'info>,
+	#[account(mut, has_one=list @ ErrorCode::WrongPage)]
+	pub page: AccountLoader<'info, ListPage>,
+	#[account(mut, has_one=list @ ErrorCode::ItemNotFound)]
+	pub item: Account<'info, ListItem>,
+	/// CHECK: last item on the page, moved into the slot of the removed item. Checked by `remove_line`
+	#[account(mut)]
+	pub moved_item: AccountInfo<'info>,
+	pub user: Signer<'info>,
+	/// CHECK: moderator PDA of the user, it only holds roles if the user is a moderator
+	#[account(seeds=[b"moderator", list.key().as_ref(), user.key().as_ref()], bump)]
+	pub moderator: AccountInfo<'info>,
+	/// CHECK: config PDA, it only holds a fee and pause flags once the config was initialized
+	#[account(seeds=[b"config"], bump)]
+	pub config: AccountInfo<'info>,
+	/// CHECK: receives the fee, checked against the config by `collect_fee`
 	#[account(mut)]
+	pub treasury: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(list_name: String)]
+pub struct Unfinish<'info> {
+	#[account(has_one=list_owner @ ErrorCode::WrongListOwner, seeds=[b"todolist", list.load()?.seed_owner.as_ref(), list_seed(&list_name, list.load()?.seed_scheme()).as_ref()], bump = list.load()?.bump)]
+	pub list: AccountLoader<'info, ListHeader>,
+	/// CHECK:
+	pub list_owner: AccountInfo<'info>,
+	#[account(mut, has_one=list @ ErrorCode::ItemNotFound)]
 	pub item: Account<'info, ListItem>,
 	pub user: Signer<'info>,
+	/// CHECK: moderator PDA of the user, it only holds roles if the user is a moderator
+	#[account(seeds=[b"moderator", list.key().as_ref(), user.key().as_ref()], bump)]
+	pub moderator: AccountInfo<'info>,
+	/// CHECK: config PDA, it only holds pause flags once the config was initialized
+	#[account(seeds=[b"config"], bump)]
+	pub config: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(list_name: String)]
+pub struct ClaimAfterTimeout<'info> {
+	#[account(mut, has_one=list_owner @ ErrorCode::WrongListOwner, seeds=[b"todolist", list.load()?.seed_owner.as_ref(), list_seed(&list_name, list.load()?.seed_scheme()).as_ref()], bump = list.load()?.bump)]
+	pub list: AccountLoader<'info, ListHeader>,
+	#[account(mut)]
+	pub list_owner: Signer<'info>,
+	#[account(mut, has_one=list @ ErrorCode::WrongPage)]
+	pub page: AccountLoader<'info, ListPage>,
+	#[account(mut, has_one=list @ ErrorCode::ItemNotFound)]
+	pub item: Account<'info, ListItem>,
+	/// CHECK: last item on the page, moved into the slot of the removed item. Checked by `remove_line`
+	#[account(mut)]
+	pub moved_item: AccountInfo<'info>,
+	/// CHECK: config PDA, it only holds a fee and pause flags once the config was initialized
+	#[account(seeds=[b"config"], bump)]
+	pub config: AccountInfo<'info>,
+	/// CHECK: receives the fee, checked against the config by `collect_fee`
+	#[account(mut)]
+	pub treasury: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitConfig<'info> {
+	#[account(init, payer=admin, space=Config::space(), seeds=[b"config"], bump)]
+	pub config: Account<'info, Config>,
+	/// Only whoever may upgrade the program becomes its admin, so the config cannot be taken by front-running
+	#[account(seeds=[crate::ID.as_ref()], bump, seeds::program=bpf_loader_upgradeable::ID, constraint=program_data.upgrade_authority_address == Some(admin.key()) @ ErrorCode::NotUpgradeAuthority)]
+	pub program_data: Account<'info, ProgramData>,
+	pub system_program: Program<'info, System>,
+	#[account(mut)]
+	pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+	#[account(mut, has_one=admin @ ErrorCode::NotAdmin, seeds=[b"config"], bump)]
+	pub config: Account<'info, Config>,
+	pub admin: Signer<'info>,
+}
+
+/// Zero-copy list header, the open items are kept on [`ListPage`]s.
+/// Lists of earlier versions keep the Borsh [`LegacyTodoList`] layout, their items are reclaimed with
+/// `reclaim_legacy_item` and the emptied list is closed with `close_legacy_list`.
+#[account(zero_copy)]
+pub struct ListHeader {
+	pub list_owner: Pubkey,
+	/// Owner that created the list, the list address is derived from it
+	pub seed_owner: Pubkey,
+	/// Nominated by `propose_owner`, becomes the owner once they `accept_owner`. Default pubkey when nobody is nominated
+	pub pending_owner: Pubkey,
+	/// May settle disputed items of this list, default pubkey for lists without arbiter
+	pub arbiter: Pubkey,
+	/// Number of items ever added, items are derived from `[b"item", list, index]`
+	pub item_count: u64,
+	/// Number of moderator PDAs of the list, they have to be removed before the list can be closed
+	pub moderator_count: u64,
+	/// Seconds after the owner finished an item until they may `claim_after_timeout` without the creator, 0 for none
+	pub review_window: i64,
+	/// Most open items the list may hold, 0 for no cap
+	pub capacity: u32,
+	/// Number of open items across all pages
+	pub len: u32,
+	/// Number of pages opened, pages are derived from `[b"page", list, page_no]`
+	pub page_count: u32,
+	pub name_len: u16,
+	/// Canonical bump of the list address, checked by every instruction instead of searching for it
+	pub bump: u8,
+	/// [`SeedScheme`] turning the name into the seed of the list address
+	pub seed_scheme: u8,
+	pub name: [u8; MAX_LIST_NAME_LEN],
+}
+
+impl ListHeader {
+	fn space() -> usize {
+		// discriminator + header
+		8 + std::mem::size_of::<ListHeader>()
+	}
+
+	pub fn name(&self) -> &str {
+		std::str::from_utf8(&self.name[..self.name_len as usize]).unwrap_or_default()
+	}
+
+	pub fn seed_scheme(&self) -> SeedScheme {
+		if self.seed_scheme == SeedScheme::Hashed as u8 {
+			SeedScheme::Hashed
+		} else {
+			SeedScheme::Truncated
+		}
+	}
+}
+
+/// Item slots of a page
+pub const PAGE_SIZE: usize = 64;
+
+/// A slice of the open items of a list. `add` appends to the last page and opens the next one once it is full,
+/// removing an item moves the last item of its page into the freed slot.
+#[account(zero_copy)]
+pub struct ListPage {
+	pub list: Pubkey,
+	/// Opened the page and paid its rent, which returns to them when the page or the list is closed
+	pub payer: Pubkey,
+	pub page_no: u32,
+	/// Number of open items, the first `len` slots of `lines`
+	pub len: u32,
+	pub lines: [Pubkey; PAGE_SIZE],
+}
+
+impl ListPage {
+	fn space() -> usize {
+		// discriminator + page
+		8 + std::mem::size_of::<ListPage>()
+	}
+
+	/// Open items on this page
+	pub fn items(&self) -> &[Pubkey] {
+		&self.lines[..self.len as usize]
+	}
+}
+
+#[account]
+pub struct ListItem {
+	pub creator: Pubkey,
+	/// Position in the order items were added to the list, part of the item address
+	pub index: u64,
+	pub creator_finished: bool,
+	pub list_owner_finished: bool,
+	/// Unix timestamp when the owner side first marked the item finished, starts the review window
+	pub owner_finished_at: Option<i64>,
+	/// `None` for bounties paid in lamports
+	pub bounty_mint: Option<Pubkey>,
+	/// Lamports or, for token bounties, the token amount held in escrow
+	pub bounty_amount: u64,
+	/// Protocol fee when the item was added, later fee changes do not apply to it
+	pub fee_bps: u16,
+	/// Unix timestamp after which anyone may `expire` the item
+	pub deadline: Option<i64>,
+	/// Set by `open_dispute`, freezes the item until the arbiter resolves it
+	pub disputed: bool,
+	/// Lamports added through `fund`, already included in `bounty_amount`
+	pub funded_amount: u64,
+	/// Number of contribution receipts that have to be settled when the item closes
+	pub funders: u16,
+	pub name: String,
+	/// Off-chain description (Arweave, IPFS or HTTPS) with the acceptance criteria of the item
+	pub uri: Option<String>,
+	/// Hash of the content behind `uri`, proving the spec did not change after posting
+	pub content_hash: [u8; 32],
+	/// List holding the item, checked with `has_one` instead of searching the list
+	pub list: Pubkey,
+	/// Page holding the item
+	pub page_no: u32,
+	/// Slot of the item on its page, updated when another item is removed and this one moves into its slot
+	pub slot: u32,
+}
+
+impl ListItem {
+	fn space(name: &str, uri: &Option<String>) -> usize {
+		// discriminator + creator pubkey + index + 2 bools + optional finish timestamp
+		8 + 32 + 8 + 1 + 1 + 1 + 8 +
+            // optional mint + amount + fee + optional deadline + disputed
+            1 + 32 + 8 + 2 + 1 + 8 + 1 +
+            // funded amount + funders + name string
+            8 + 2 + 4 + name.len() +
+            // optional uri string + content hash
+            1 + 4 + uri.as_ref().map_or(0, |uri| uri.len()) + 32 +
+            // list pubkey + page number + slot
+            32 + 4 + 4
+	}
+}
+
+#[account]
+pub struct Contribution {
+	pub item: Pubkey,
+	pub funder: Pubkey,
+	pub amount: u64,
+}
+
+impl Contribution {
+	fn space() -> usize {
+		// discriminator + item pubkey + funder pubkey + amount
+		8 + 32 + 32 + 8
+	}
 }
 
 #[account]
-pub struct TodoList {
+pub struct Moderator {
+	pub list: Pubkey,
+	pub moderator: Pubkey,
+	pub can_cancel: bool,
+	pub can_finish: bool,
+}
+
+impl Moderator {
+	fn space() -> usize {
+		// discriminator + list pubkey + moderator pubkey + 2 bools
+		8 + 32 + 32 + 1 + 1
+	}
+}
+
+/// Borsh `TodoList` written by earlier versions, before lists kept their items on pages.
+/// Only read to reclaim the bounties of its items and close it.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyTodoList {
 	pub list_owner: Pubkey,
 	pub capacity: u16,
 	pub bump: u8,
@@ -180,32 +1706,285 @@ pub struct TodoList {
 	pub lines: Vec<Pubkey>,
 }
 
-impl TodoList {
-	fn space(name: &str, capacity: u16) -> usize {
-		// discriminator + owner pubkey + bump + capacity
-		8 + 32 + 1 + 2 +
-            // name string
-            4 + name.len() +
-            // vec of item pubkeys
-            4 + (capacity as usize) * std::mem::size_of::<Pubkey>()
+impl LegacyTodoList {
+	pub fn discriminator() -> [u8; 8] {
+		let mut discriminator = [0; 8];
+		discriminator.copy_from_slice(&hash(b"account:TodoList").to_bytes()[..8]);
+		discriminator
+	}
+
+	fn load(info: &AccountInfo) -> Result<Self> {
+		let data = info.try_borrow_data()?;
+		require!(data.len() >= 8 && data[..8] == Self::discriminator(), ErrorCode::NotLegacyAccount);
+		// The account was allocated for `capacity` lines, the unused tail is left out
+		Ok(Self::deserialize(&mut &data[8..])?)
+	}
+
+	fn store(&self, info: &AccountInfo) -> Result<()> {
+		self.serialize(&mut &mut info.try_borrow_mut_data()?[8..])?;
+		Ok(())
 	}
 }
 
-#[account]
-pub struct ListItem {
+/// Borsh `ListItem` written by earlier versions, it shares its discriminator with [`ListItem`].
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyListItem {
 	pub creator: Pubkey,
 	pub creator_finished: bool,
 	pub list_owner_finished: bool,
 	pub name: String,
 }
 
-impl ListItem {
-	fn space(name: &str) -> usize {
-		// discriminator + creator pubkey + 2 bools + name string
-		8 + 32 + 1 + 1 + 4 + name.len()
+impl LegacyListItem {
+	fn load(info: &AccountInfo) -> Result<Self> {
+		let data = info.try_borrow_data()?;
+		require!(data.len() >= 8 && data[..8] == ListItem::discriminator(), ErrorCode::NotLegacyAccount);
+		// Legacy items were allocated for their name exactly, current items do not parse without a tail
+		Self::try_from_slice(&data[8..]).map_err(|_| error!(ErrorCode::NotLegacyAccount))
 	}
 }
 
+/// Settings of the whole deployment, stored at `[b"config"]`
+#[account]
+pub struct Config {
+	/// May change the config
+	pub admin: Pubkey,
+	/// Share of lamport bounties paid to `treasury` when an item is paid out to the list owner,
+	/// items keep the fee they were added with
+	pub fee_bps: u16,
+	pub treasury: Pubkey,
+	/// Stops every instruction but refunds while `refunds_open`
+	pub paused: bool,
+	/// Stops adding and funding items
+	pub deposits_paused: bool,
+	/// Stops paying out items, and refunding them unless `refunds_open`
+	pub payouts_paused: bool,
+	/// Keeps cancelling, expiring and closing lists open while paused, so users can get their bounties back
+	pub refunds_open: bool,
+}
+
+impl Config {
+	fn space() -> usize {
+		// discriminator + admin pubkey + fee + treasury pubkey + pause flags
+		8 + 32 + 2 + 32 + 4
+	}
+
+	fn pauses(&self, operation: Operation) -> bool {
+		match operation {
+			Operation::Refund if self.refunds_open => false,
+			Operation::Deposit => self.paused || self.deposits_paused,
+			Operation::Payout | Operation::Refund => self.paused || self.payouts_paused,
+			Operation::Other => self.paused,
+		}
+	}
+
+	fn set_fee(&mut self, fee_bps: u16, treasury: Pubkey) -> Result<()> {
+		require!(fee_bps <= MAX_FEE_BPS, ErrorCode::InvalidFee);
+		self.fee_bps = fee_bps;
+		self.treasury = treasury;
+
+		emit!(ConfigUpdated {
+			admin: self.admin,
+			fee_bps,
+			treasury,
+		});
+
+		Ok(())
+	}
+}
+
+/// What an instruction does with bounties, decides which pause flags stop it
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Operation {
+	/// Adds to a bounty
+	Deposit,
+	/// Pays a bounty out to the list owner
+	Payout,
+	/// Returns a bounty to whoever put it up
+	Refund,
+	/// Moves no bounty
+	Other,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum SeedScheme {
+	/// Name truncated to 32 bytes, names sharing their first 32 bytes collide
+	Truncated,
+	/// SHA-256 of the full name
+	Hashed,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum FinishSide {
+	Creator,
+	ListOwner,
+}
+
+#[event]
+pub struct ListCreated {
+	pub list: Pubkey,
+	pub list_owner: Pubkey,
+	pub name: String,
+	/// `None` for lists without cap
+	pub capacity: Option<u32>,
+}
+
+#[event]
+pub struct ListResized {
+	pub list: Pubkey,
+	pub capacity: Option<u32>,
+}
+
+#[event]
+pub struct ListClosed {
+	pub list: Pubkey,
+}
+
+#[event]
+pub struct PageClosed {
+	pub list: Pubkey,
+	pub page_no: u32,
+}
+
+#[event]
+pub struct LegacyItemReclaimed {
+	pub list: Pubkey,
+	pub item: Pubkey,
+	pub refund_recipient: Pubkey,
+}
+
+#[event]
+pub struct OwnerProposed {
+	pub list: Pubkey,
+	pub pending_owner: Option<Pubkey>,
+}
+
+#[event]
+pub struct OwnerAccepted {
+	pub list: Pubkey,
+	pub previous_owner: Pubkey,
+	pub new_owner: Pubkey,
+}
+
+#[event]
+pub struct ModeratorSet {
+	pub list: Pubkey,
+	pub moderator: Pubkey,
+	pub can_cancel: bool,
+	pub can_finish: bool,
+}
+
+#[event]
+pub struct ModeratorRemoved {
+	pub list: Pubkey,
+	pub moderator: Pubkey,
+}
+
+#[event]
+pub struct ItemAdded {
+	pub list: Pubkey,
+	pub item: Pubkey,
+	pub creator: Pubkey,
+	/// `None` for bounties paid in lamports
+	pub bounty_mint: Option<Pubkey>,
+	pub bounty: u64,
+}
+
+#[event]
+pub struct ItemEdited {
+	pub list: Pubkey,
+	pub item: Pubkey,
+	pub name: String,
+	pub uri: Option<String>,
+	pub content_hash: [u8; 32],
+}
+
+#[event]
+pub struct ItemFunded {
+	pub list: Pubkey,
+	pub item: Pubkey,
+	pub funder: Pubkey,
+	pub amount: u64,
+	/// Bounty of the item including this contribution
+	pub bounty: u64,
+}
+
+#[event]
+pub struct ItemCancelled {
+	pub list: Pubkey,
+	pub item: Pubkey,
+	pub cancelled_by: Pubkey,
+	pub refund_recipient: Pubkey,
+}
+
+#[event]
+pub struct ItemExpired {
+	pub list: Pubkey,
+	pub item: Pubkey,
+	pub refund_recipient: Pubkey,
+}
+
+#[event]
+pub struct ItemFinishMarked {
+	pub list: Pubkey,
+	pub item: Pubkey,
+	pub marked_by: Pubkey,
+	pub side: FinishSide,
+}
+
+#[event]
+pub struct ItemFinishRevoked {
+	pub list: Pubkey,
+	pub item: Pubkey,
+	pub revoked_by: Pubkey,
+	pub side: FinishSide,
+}
+
+#[event]
+pub struct ItemPaid {
+	pub list: Pubkey,
+	pub item: Pubkey,
+	pub recipient: Pubkey,
+	pub bounty_mint: Option<Pubkey>,
+	pub amount: u64,
+}
+
+#[event]
+pub struct DisputeOpened {
+	pub list: Pubkey,
+	pub item: Pubkey,
+	pub opened_by: Pubkey,
+}
+
+#[event]
+pub struct DisputeResolved {
+	pub list: Pubkey,
+	pub item: Pubkey,
+	pub owner_share_bps: u16,
+}
+
+#[event]
+pub struct ConfigUpdated {
+	pub admin: Pubkey,
+	pub fee_bps: u16,
+	pub treasury: Pubkey,
+}
+
+#[event]
+pub struct PauseUpdated {
+	pub paused: bool,
+	pub deposits_paused: bool,
+	pub payouts_paused: bool,
+	pub refunds_open: bool,
+}
+
+#[event]
+pub struct FeeCollected {
+	pub item: Pubkey,
+	pub treasury: Pubkey,
+	pub amount: u64,
+}
+
 #[error_code]
 pub enum ErrorCode {
 	#[msg("This list is full")]
@@ -222,4 +2001,106 @@ pub enum ErrorCode {
 	WrongListOwner,
 	#[msg("Specified item creator does not match the pubkey in the item")]
 	WrongItemCreator,
+	#[msg("Token bounties require the escrow accounts and a destination token account")]
+	MissingEscrowAccounts,
+	#[msg("Specified escrow accounts do not belong to the item")]
+	WrongEscrow,
+	#[msg("Token account does not hold the bounty mint for the expected owner")]
+	WrongTokenAccount,
+	#[msg("Deadline must be in the future")]
+	DeadlineInPast,
+	#[msg("Item has no deadline")]
+	NoDeadline,
+	#[msg("Item deadline has not passed yet")]
+	DeadlineNotReached,
+	#[msg("This list has no arbiter")]
+	NoArbiter,
+	#[msg("Only the list owner or item creator may dispute an item")]
+	DisputePermissions,
+	#[msg("Item is disputed and frozen until the arbiter resolves it")]
+	ItemDisputed,
+	#[msg("Item is not disputed")]
+	ItemNotDisputed,
+	#[msg("Only the list arbiter may resolve disputes")]
+	NotArbiter,
+	#[msg("Owner share must be between 0 and 10000 basis points")]
+	InvalidShare,
+	#[msg("This instruction only supports lamport bounties")]
+	UnsupportedTokenBounty,
+	#[msg("Item has reached the maximum number of funders")]
+	TooManyFunders,
+	#[msg("Every contribution receipt of the item and its funder must be passed")]
+	MissingContributions,
+	#[msg("Specified contribution does not belong to the item and funder")]
+	WrongContribution,
+	#[msg("Every remaining item of the list must be passed with its creator to close it")]
+	ListNotEmpty,
+	#[msg("Token, crowdfunded and disputed items must be cancelled before closing the list")]
+	ItemNeedsCancel,
+	#[msg("Capacity must be positive and hold the items already in the list")]
+	CapacityTooSmall,
+	#[msg("Only the nominated owner may accept the list")]
+	NotPendingOwner,
+	#[msg("This moderator may not cancel items")]
+	ModeratorCannotCancel,
+	#[msg("This moderator may not finish items")]
+	ModeratorCannotFinish,
+	#[msg("Remove the moderators of the list before closing it")]
+	ModeratorsLeft,
+	#[msg("Only the item creator may edit an item")]
+	EditPermissions,
+	#[msg("Item cannot be edited once it is marked finished")]
+	ItemLocked,
+	#[msg("Item cannot be edited once it has funders")]
+	ItemHasFunders,
+	#[msg("Item URI must be between 1 and 200 bytes")]
+	InvalidUri,
+	#[msg("Review window must be a positive number of seconds")]
+	InvalidReviewWindow,
+	#[msg("A review window needs an arbiter to settle disputes")]
+	ReviewWindowNeedsArbiter,
+	#[msg("The list owner cannot be the arbiter of their own list")]
+	OwnerIsArbiter,
+	#[msg("This list has no review window")]
+	NoReviewWindow,
+	#[msg("The list owner has not marked this item finished")]
+	OwnerNotFinished,
+	#[msg("Review window of the item has not passed yet")]
+	ReviewWindowOpen,
+	#[msg("Item was marked finished by the list owner, the creator can only dispute it")]
+	OwnerAlreadyFinished,
+	#[msg("Item is not marked finished by this side")]
+	NotMarkedFinished,
+	#[msg("List name cannot be empty")]
+	ListNameEmpty,
+	#[msg("List name must be at most 64 bytes")]
+	ListNameTooLong,
+	#[msg("Truncated list seeds cannot cut the name inside a character, use the hashed seed scheme")]
+	ListNameSplitsCharacter,
+	#[msg("Page does not belong to this list, does not hold the item or is not its next page")]
+	WrongPage,
+	#[msg("This page is full, add the item to the next page")]
+	PageFull,
+	#[msg("Every page of the list must be passed in page order to close it")]
+	MissingPages,
+	#[msg("Specified page payer does not match the pubkey in the page")]
+	WrongPagePayer,
+	#[msg("Moved item must be the last item on the page of the removed item")]
+	WrongMovedItem,
+	#[msg("Only the last page of a list can be closed")]
+	NotLastPage,
+	#[msg("Page still holds items")]
+	PageNotEmpty,
+	#[msg("Account is not a list or item written by an earlier version")]
+	NotLegacyAccount,
+	#[msg("Only the config admin may change the config")]
+	NotAdmin,
+	#[msg("Only the upgrade authority of the program may initialize the config")]
+	NotUpgradeAuthority,
+	#[msg("Fee must be between 0 and 1000 basis points")]
+	InvalidFee,
+	#[msg("Specified treasury does not match the pubkey in the config")]
+	WrongTreasury,
+	#[msg("The program is paused")]
+	ProgramPaused,
 }
@@ -0,0 +1,2629 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{hash::hash, instruction::Instruction, system_instruction, system_program, sysvar};
+use anchor_lang::{AccountDeserialize, InstructionData};
+use anchor_spl::token::spl_token;
+use solana_program_test::{processor, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+	account::AccountSharedData,
+	bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+	instruction::InstructionError,
+	program_pack::Pack,
+	signature::{Keypair, Signer},
+	transaction::{Transaction, TransactionError},
+	transport::TransportError,
+};
+use todos::{Config, Contribution, ListHeader, ListItem, ListPage, SeedScheme};
+
+const LPS: u64 = 1_000_000_000;
+
+// { == Helper functions ==>
+async fn setup() -> ProgramTestContext {
+	ProgramTest::new("todos", todos::ID, processor!(todos::entry)).start_with_context().await
+}
+
+async fn create_user(ctx: &mut ProgramTestContext) -> Keypair {
+	let user = Keypair::new();
+	let fund = system_instruction::transfer(&ctx.payer.pubkey(), &user.pubkey(), 10 * LPS);
+	send(ctx, &[fund], &[]).await.unwrap();
+	user
+}
+
+async fn create_users<const N: usize>(ctx: &mut ProgramTestContext) -> [Keypair; N] {
+	let mut users = Vec::with_capacity(N);
+	for _ in 0..N {
+		users.push(create_user(ctx).await);
+	}
+	users.try_into().unwrap_or_else(|_| unreachable!())
+}
+
+/// Sends the instructions with the context payer covering the fees,
+/// so the lamport balances of the signers only change by what the program moves.
+async fn send(ctx: &mut ProgramTestContext, instructions: &[Instruction], signers: &[&Keypair]) -> std::result::Result<(), TransportError> {
+	let blockhash = ctx.banks_client.get_latest_blockhash().await.unwrap();
+	let mut all_signers = vec![&ctx.payer];
+	all_signers.extend_from_slice(signers);
+
+	let tx = Transaction::new_signed_with_payer(instructions, Some(&ctx.payer.pubkey()), &all_signers, blockhash);
+	ctx.banks_client.process_transaction(tx).await
+}
+
+/// Moves to a new slot so that an instruction identical to an earlier one is not deduplicated.
+async fn next_slot(ctx: &mut ProgramTestContext) {
+	let clock: Clock = ctx.banks_client.get_sysvar().await.unwrap();
+	ctx.warp_to_slot(clock.slot + 2).unwrap();
+}
+
+fn error_code(result: std::result::Result<(), TransportError>) -> u32 {
+	match result.unwrap_err() {
+		TransportError::TransactionError(TransactionError::InstructionError(_, InstructionError::Custom(code))) => code,
+		err => panic!("unexpected error: {:?}", err),
+	}
+}
+
+fn program_error(code: todos::ErrorCode) -> u32 {
+	code.into()
+}
+
+fn anchor_error(code: anchor_lang::error::ErrorCode) -> u32 {
+	code.into()
+}
+
+async fn balance(ctx: &mut ProgramTestContext, pubkey: &Pubkey) -> u64 {
+	ctx.banks_client.get_balance(*pubkey).await.unwrap()
+}
+
+async fn fetch<T: AccountDeserialize>(ctx: &mut ProgramTestContext, pubkey: &Pubkey) -> T {
+	let account = ctx.banks_client.get_account(*pubkey).await.unwrap().expect("account exists");
+	T::try_deserialize(&mut account.data.as_slice()).unwrap()
+}
+
+/// Reads a zero-copy account, lists and pages are not Borsh encoded.
+async fn fetch_zero_copy<T: bytemuck::Pod>(ctx: &mut ProgramTestContext, pubkey: &Pubkey) -> T {
+	let account = ctx.banks_client.get_account(*pubkey).await.unwrap().expect("account exists");
+	bytemuck::pod_read_unaligned(&account.data[8..8 + std::mem::size_of::<T>()])
+}
+
+/// Reads the header of a list and the addresses of its open items on all of its pages.
+async fn fetch_list(ctx: &mut ProgramTestContext, pubkey: &Pubkey) -> (ListHeader, Vec<Pubkey>) {
+	let list: ListHeader = fetch_zero_copy(ctx, pubkey).await;
+
+	let mut lines = Vec::new();
+	for page_no in 0..list.page_count {
+		let page: ListPage = fetch_zero_copy(ctx, &page_address(pubkey, page_no)).await;
+		lines.extend_from_slice(page.items());
+	}
+	(list, lines)
+}
+
+async fn token_balance(ctx: &mut ProgramTestContext, pubkey: &Pubkey) -> u64 {
+	let account = ctx.banks_client.get_account(*pubkey).await.unwrap().expect("token account exists");
+	spl_token::state::Account::unpack(&account.data).unwrap().amount
+}
+
+fn list_address(owner: &Pubkey, name: &str, scheme: SeedScheme) -> (Pubkey, u8) {
+	let seed = todos::list_seed(name, scheme);
+	Pubkey::find_program_address(&[b"todolist", owner.as_ref(), &seed], &todos::ID)
+}
+
+fn item_address(list: &Pubkey, index: u64) -> Pubkey {
+	Pubkey::find_program_address(&[b"item", list.as_ref(), &index.to_le_bytes()], &todos::ID).0
+}
+
+fn page_address(list: &Pubkey, page_no: u32) -> Pubkey {
+	Pubkey::find_program_address(&[b"page", list.as_ref(), &page_no.to_le_bytes()], &todos::ID).0
+}
+
+/// Page the instruction builders pass, tests keep their items on the first page unless they are about paging
+fn first_page(list: &TestList) -> Pubkey {
+	page_address(&list.address, 0)
+}
+
+/// Builders removing an item pass the item itself as the moved item, which holds while it is the last item of its page.
+/// Replaces it with the last item of the page for removals from another slot.
+fn moving(mut ix: Instruction, moved_item: &Pubkey) -> Instruction {
+	// list, list_owner, page, item, moved_item
+	ix.accounts[4].pubkey = *moved_item;
+	ix
+}
+
+fn config_address() -> Pubkey {
+	Pubkey::find_program_address(&[b"config"], &todos::ID).0
+}
+
+/// Treasury the builders pass, tests about the fee make it the treasury of the config
+fn treasury() -> Pubkey {
+	Pubkey::new_from_array([7; 32])
+}
+
+fn moderator_address(list: &Pubkey, user: &Pubkey) -> Pubkey {
+	Pubkey::find_program_address(&[b"moderator", list.as_ref(), user.as_ref()], &todos::ID).0
+}
+
+fn contribution_address(item: &Pubkey, funder: &Pubkey) -> Pubkey {
+	Pubkey::find_program_address(&[b"contribution", item.as_ref(), funder.as_ref()], &todos::ID).0
+}
+
+fn writable(pubkey: Pubkey) -> AccountMeta {
+	AccountMeta::new(pubkey, false)
+}
+
+fn readonly(pubkey: Pubkey) -> AccountMeta {
+	AccountMeta::new_readonly(pubkey, false)
+}
+
+/// `[escrow, escrow_authority, token_program, ..destinations]` as expected by token payouts
+fn escrow_accounts(item: &Pubkey, destinations: &[Pubkey]) -> Vec<AccountMeta> {
+	let escrow = Pubkey::find_program_address(&[b"escrow", item.as_ref()], &todos::ID).0;
+	let escrow_authority = Pubkey::find_program_address(&[b"escrow_authority", item.as_ref()], &todos::ID).0;
+
+	let mut accounts = vec![writable(escrow), readonly(escrow_authority), readonly(spl_token::ID)];
+	accounts.extend(destinations.iter().copied().map(writable));
+	accounts
+}
+
+fn contribution_accounts(item: &Pubkey, funders: &[Pubkey]) -> Vec<AccountMeta> {
+	funders
+		.iter()
+		.flat_map(|funder| [writable(contribution_address(item, funder)), writable(*funder)])
+		.collect()
+}
+
+struct TestList {
+	address: Pubkey,
+	owner: Pubkey,
+	name: String,
+}
+
+fn new_list_ix(
+	owner: &Pubkey,
+	name: &str,
+	capacity: Option<u32>,
+	arbiter: Option<Pubkey>,
+	review_window: Option<i64>,
+	seed_scheme: SeedScheme,
+) -> (Instruction, Pubkey) {
+	let (address, _) = list_address(owner, name, seed_scheme);
+	let ix = Instruction {
+		program_id: todos::ID,
+		accounts: todos::accounts::NewList {
+			list: address,
+			system_program: system_program::ID,
+			user: *owner,
+			config: config_address(),
+		}
+		.to_account_metas(None),
+		data: todos::instruction::NewList {
+			name: name.to_string(),
+			capacity,
+			arbiter,
+			review_window,
+			seed_scheme,
+		}
+		.data(),
+	};
+	(ix, address)
+}
+
+async fn create_list(
+	ctx: &mut ProgramTestContext,
+	owner: &Keypair,
+	name: &str,
+	capacity: Option<u32>,
+	arbiter: Option<Pubkey>,
+	review_window: Option<i64>,
+) -> TestList {
+	let (ix, address) = new_list_ix(&owner.pubkey(), name, capacity, arbiter, review_window, SeedScheme::Hashed);
+	send(ctx, &[ix], &[owner]).await.unwrap();
+
+	TestList {
+		address,
+		owner: owner.pubkey(),
+		name: name.to_string(),
+	}
+}
+
+async fn next_item(ctx: &mut ProgramTestContext, list: &TestList) -> Pubkey {
+	let list_data: ListHeader = fetch_zero_copy(ctx, &list.address).await;
+	item_address(&list.address, list_data.item_count)
+}
+
+/// Items are added to the last page until it is full
+async fn next_page(ctx: &mut ProgramTestContext, list: &TestList) -> u32 {
+	let list_data: ListHeader = fetch_zero_copy(ctx, &list.address).await;
+	if list_data.page_count == 0 {
+		return 0;
+	}
+	let last_page: ListPage = fetch_zero_copy(ctx, &page_address(&list.address, list_data.page_count - 1)).await;
+	if (last_page.len as usize) < todos::PAGE_SIZE {
+		list_data.page_count - 1
+	} else {
+		list_data.page_count
+	}
+}
+
+async fn add_item(
+	ctx: &mut ProgramTestContext,
+	list: &TestList,
+	user: &Keypair,
+	name: &str,
+	bounty: u64,
+	deadline: Option<i64>,
+) -> std::result::Result<Pubkey, TransportError> {
+	let page_no = next_page(ctx, list).await;
+	let data = todos::instruction::Add {
+		_list_name: list.name.clone(),
+		item_name: name.to_string(),
+		bounty,
+		deadline,
+		uri: None,
+		content_hash: [0; 32],
+		page_no,
+	};
+	send_add(ctx, list, user, data).await
+}
+
+/// Adds the next item of `list` to the page `data` names, for tests that set the description or another page.
+async fn send_add(
+	ctx: &mut ProgramTestContext,
+	list: &TestList,
+	user: &Keypair,
+	data: todos::instruction::Add,
+) -> std::result::Result<Pubkey, TransportError> {
+	let item = next_item(ctx, list).await;
+	let ix = Instruction {
+		program_id: todos::ID,
+		accounts: todos::accounts::Add {
+			list: list.address,
+			list_owner: list.owner,
+			page: page_address(&list.address, data.page_no),
+			item,
+			system_program: system_program::ID,
+			user: user.pubkey(),
+			config: config_address(),
+		}
+		.to_account_metas(None),
+		data: data.data(),
+	};
+	send(ctx, &[ix], &[user]).await.map(|_| item)
+}
+
+fn cancel_ix(list: &TestList, item: &Pubkey, item_creator: &Pubkey, user: &Pubkey, remaining: Vec<AccountMeta>) -> Instruction {
+	let mut accounts = todos::accounts::Cancel {
+		list: list.address,
+		list_owner: list.owner,
+		page: first_page(list),
+		item: *item,
+		moved_item: *item,
+		item_creator: *item_creator,
+		user: *user,
+		moderator: moderator_address(&list.address, user),
+		config: config_address(),
+	}
+	.to_account_metas(None);
+	accounts.extend(remaining);
+
+	Instruction {
+		program_id: todos::ID,
+		accounts,
+		data: todos::instruction::Cancel {
+			_list_name: list.name.clone(),
+		}
+		.data(),
+	}
+}
+
+fn finish_ix(list: &TestList, list_owner: &Pubkey, item: &Pubkey, user: &Pubkey, remaining: Vec<AccountMeta>) -> Instruction {
+	let mut accounts = todos::accounts::Finish {
+		list: list.address,
+		list_owner: *list_owner,
+		page: first_page(list),
+		item: *item,
+		moved_item: *item,
+		user: *user,
+		moderator: moderator_address(&list.address, user),
+		config: config_address(),
+		treasury: treasury(),
+	}
+	.to_account_metas(None);
+	accounts.extend(remaining);
+
+	Instruction {
+		program_id: todos::ID,
+		accounts,
+		data: todos::instruction::Finish {
+			_list_name: list.name.clone(),
+		}
+		.data(),
+	}
+}
+
+fn unfinish_ix(list: &TestList, item: &Pubkey, user: &Pubkey) -> Instruction {
+	Instruction {
+		program_id: todos::ID,
+		accounts: todos::accounts::Unfinish {
+			list: list.address,
+			list_owner: list.owner,
+			item: *item,
+			user: *user,
+			moderator: moderator_address(&list.address, user),
+			config: config_address(),
+		}
+		.to_account_metas(None),
+		data: todos::instruction::Unfinish {
+			_list_name: list.name.clone(),
+		}
+		.data(),
+	}
+}
+
+fn expire_ix(list: &TestList, item: &Pubkey, item_creator: &Pubkey) -> Instruction {
+	Instruction {
+		program_id: todos::ID,
+		accounts: todos::accounts::Expire {
+			list: list.address,
+			list_owner: list.owner,
+			page: first_page(list),
+			item: *item,
+			moved_item: *item,
+			item_creator: *item_creator,
+			config: config_address(),
+		}
+		.to_account_metas(None),
+		data: todos::instruction::Expire {
+			_list_name: list.name.clone(),
+		}
+		.data(),
+	}
+}
+
+fn claim_after_timeout_ix(list: &TestList, item: &Pubkey, remaining: Vec<AccountMeta>) -> Instruction {
+	let mut accounts = todos::accounts::ClaimAfterTimeout {
+		list: list.address,
+		list_owner: list.owner,
+		page: first_page(list),
+		item: *item,
+		moved_item: *item,
+		config: config_address(),
+		treasury: treasury(),
+	}
+	.to_account_metas(None);
+	accounts.extend(remaining);
+
+	Instruction {
+		program_id: todos::ID,
+		accounts,
+		data: todos::instruction::ClaimAfterTimeout {
+			_list_name: list.name.clone(),
+		}
+		.data(),
+	}
+}
+
+fn open_dispute_ix(list: &TestList, item: &Pubkey, user: &Pubkey) -> Instruction {
+	Instruction {
+		program_id: todos::ID,
+		accounts: todos::accounts::OpenDispute {
+			list: list.address,
+			list_owner: list.owner,
+			item: *item,
+			user: *user,
+			config: config_address(),
+		}
+		.to_account_metas(None),
+		data: todos::instruction::OpenDispute {
+			_list_name: list.name.clone(),
+		}
+		.data(),
+	}
+}
+
+fn resolve_dispute_ix(
+	list: &TestList,
+	item: &Pubkey,
+	item_creator: &Pubkey,
+	arbiter: &Pubkey,
+	owner_share_bps: u16,
+	remaining: Vec<AccountMeta>,
+) -> Instruction {
+	let mut accounts = todos::accounts::ResolveDispute {
+		list: list.address,
+		list_owner: list.owner,
+		page: first_page(list),
+		item: *item,
+		moved_item: *item,
+		item_creator: *item_creator,
+		arbiter: *arbiter,
+		config: config_address(),
+		treasury: treasury(),
+	}
+	.to_account_metas(None);
+	accounts.extend(remaining);
+
+	Instruction {
+		program_id: todos::ID,
+		accounts,
+		data: todos::instruction::ResolveDispute {
+			_list_name: list.name.clone(),
+			owner_share_bps,
+		}
+		.data(),
+	}
+}
+
+fn fund_ix(list: &TestList, item: &Pubkey, funder: &Pubkey, amount: u64) -> Instruction {
+	Instruction {
+		program_id: todos::ID,
+		accounts: todos::accounts::Fund {
+			list: list.address,
+			list_owner: list.owner,
+			item: *item,
+			contribution: contribution_address(item, funder),
+			system_program: system_program::ID,
+			funder: *funder,
+			config: config_address(),
+		}
+		.to_account_metas(None),
+		data: todos::instruction::Fund {
+			_list_name: list.name.clone(),
+			amount,
+		}
+		.data(),
+	}
+}
+
+/// `page_payers` holds the payer of every page of the list, in page order
+fn close_list_ix(list: &TestList, list_owner: &Pubkey, page_payers: &[Pubkey], items: &[(Pubkey, Pubkey)]) -> Instruction {
+	let mut accounts = todos::accounts::CloseList {
+		list: list.address,
+		list_owner: *list_owner,
+		config: config_address(),
+	}
+	.to_account_metas(None);
+	accounts.extend(
+		page_payers
+			.iter()
+			.zip(0..)
+			.flat_map(|(payer, page_no)| [writable(page_address(&list.address, page_no)), writable(*payer)]),
+	);
+	accounts.extend(items.iter().flat_map(|(item, creator)| [writable(*item), writable(*creator)]));
+
+	Instruction {
+		program_id: todos::ID,
+		accounts,
+		data: todos::instruction::CloseList {
+			_list_name: list.name.clone(),
+		}
+		.data(),
+	}
+}
+
+fn close_page_ix(list: &TestList, page_no: u32, payer: &Pubkey) -> Instruction {
+	Instruction {
+		program_id: todos::ID,
+		accounts: todos::accounts::ClosePage {
+			list: list.address,
+			page: page_address(&list.address, page_no),
+			payer: *payer,
+			config: config_address(),
+		}
+		.to_account_metas(None),
+		data: todos::instruction::ClosePage {
+			_list_name: list.name.clone(),
+		}
+		.data(),
+	}
+}
+
+fn resize_list_ix(list: &TestList, capacity: Option<u32>) -> Instruction {
+	Instruction {
+		program_id: todos::ID,
+		accounts: todos::accounts::ResizeList {
+			list: list.address,
+			list_owner: list.owner,
+			config: config_address(),
+		}
+		.to_account_metas(None),
+		data: todos::instruction::ResizeList {
+			_list_name: list.name.clone(),
+			capacity,
+		}
+		.data(),
+	}
+}
+
+fn propose_owner_ix(list: &TestList, list_owner: &Pubkey, new_owner: Option<Pubkey>) -> Instruction {
+	Instruction {
+		program_id: todos::ID,
+		accounts: todos::accounts::ProposeOwner {
+			list: list.address,
+			list_owner: *list_owner,
+			config: config_address(),
+		}
+		.to_account_metas(None),
+		data: todos::instruction::ProposeOwner {
+			_list_name: list.name.clone(),
+			new_owner,
+		}
+		.data(),
+	}
+}
+
+fn accept_owner_ix(list: &TestList, new_owner: &Pubkey) -> Instruction {
+	Instruction {
+		program_id: todos::ID,
+		accounts: todos::accounts::AcceptOwner {
+			list: list.address,
+			new_owner: *new_owner,
+			config: config_address(),
+		}
+		.to_account_metas(None),
+		data: todos::instruction::AcceptOwner {
+			_list_name: list.name.clone(),
+		}
+		.data(),
+	}
+}
+
+fn set_moderator_ix(list: &TestList, moderator: &Pubkey, can_cancel: bool, can_finish: bool) -> Instruction {
+	Instruction {
+		program_id: todos::ID,
+		accounts: todos::accounts::SetModerator {
+			list: list.address,
+			moderator: moderator_address(&list.address, moderator),
+			system_program: system_program::ID,
+			list_owner: list.owner,
+			config: config_address(),
+		}
+		.to_account_metas(None),
+		data: todos::instruction::SetModerator {
+			_list_name: list.name.clone(),
+			moderator_key: *moderator,
+			can_cancel,
+			can_finish,
+		}
+		.data(),
+	}
+}
+
+fn remove_moderator_ix(list: &TestList, moderator: &Pubkey) -> Instruction {
+	Instruction {
+		program_id: todos::ID,
+		accounts: todos::accounts::RemoveModerator {
+			list: list.address,
+			moderator: moderator_address(&list.address, moderator),
+			list_owner: list.owner,
+			config: config_address(),
+		}
+		.to_account_metas(None),
+		data: todos::instruction::RemoveModerator {
+			_list_name: list.name.clone(),
+			moderator_key: *moderator,
+		}
+		.data(),
+	}
+}
+
+fn edit_item_ix(list: &TestList, item: &Pubkey, item_creator: &Pubkey, name: &str, uri: Option<String>) -> Instruction {
+	Instruction {
+		program_id: todos::ID,
+		accounts: todos::accounts::EditItem {
+			list: list.address,
+			list_owner: list.owner,
+			item: *item,
+			system_program: system_program::ID,
+			item_creator: *item_creator,
+			config: config_address(),
+		}
+		.to_account_metas(None),
+		data: todos::instruction::EditItem {
+			_list_name: list.name.clone(),
+			item_name: name.to_string(),
+			uri,
+			content_hash: [1; 32],
+		}
+		.data(),
+	}
+}
+
+fn program_data_address() -> Pubkey {
+	Pubkey::find_program_address(&[todos::ID.as_ref()], &bpf_loader_upgradeable::id()).0
+}
+
+/// `ProgramTest` loads the program natively, this writes the program data account an upgradeable deploy by `authority` has.
+fn set_upgrade_authority(ctx: &mut ProgramTestContext, authority: &Pubkey) {
+	let state = UpgradeableLoaderState::ProgramData {
+		slot: 0,
+		upgrade_authority_address: Some(*authority),
+	};
+	let account = AccountSharedData::new_data(LPS, &state, &bpf_loader_upgradeable::id()).unwrap();
+	ctx.set_account(&program_data_address(), &account);
+}
+
+/// Initializes the config with `admin` as upgrade authority of the program.
+async fn init_config(ctx: &mut ProgramTestContext, admin: &Keypair, fee_bps: u16, treasury: Pubkey) {
+	set_upgrade_authority(ctx, &admin.pubkey());
+	send(ctx, &[init_config_ix(&admin.pubkey(), fee_bps, treasury)], &[admin])
+		.await
+		.unwrap();
+}
+
+fn init_config_ix(admin: &Pubkey, fee_bps: u16, treasury: Pubkey) -> Instruction {
+	Instruction {
+		program_id: todos::ID,
+		accounts: todos::accounts::InitConfig {
+			config: config_address(),
+			program_data: program_data_address(),
+			system_program: system_program::ID,
+			admin: *admin,
+		}
+		.to_account_metas(None),
+		data: todos::instruction::InitConfig { fee_bps, treasury }.data(),
+	}
+}
+
+fn update_config_ix(admin: &Pubkey, fee_bps: u16, treasury: Pubkey) -> Instruction {
+	Instruction {
+		program_id: todos::ID,
+		accounts: todos::accounts::UpdateConfig {
+			config: config_address(),
+			admin: *admin,
+		}
+		.to_account_metas(None),
+		data: todos::instruction::UpdateConfig { fee_bps, treasury }.data(),
+	}
+}
+
+fn set_pause_ix(admin: &Pubkey, paused: bool, deposits_paused: bool, payouts_paused: bool, refunds_open: bool) -> Instruction {
+	Instruction {
+		program_id: todos::ID,
+		accounts: todos::accounts::UpdateConfig {
+			config: config_address(),
+			admin: *admin,
+		}
+		.to_account_metas(None),
+		data: todos::instruction::SetPause {
+			paused,
+			deposits_paused,
+			payouts_paused,
+			refunds_open,
+		}
+		.data(),
+	}
+}
+
+/// Creates a mint with `authority` and one token account per owner, each holding `amount` tokens.
+async fn create_token_accounts(ctx: &mut ProgramTestContext, authority: &Keypair, owners: &[&Keypair], amount: u64) -> (Pubkey, Vec<Pubkey>) {
+	let rent = ctx.banks_client.get_rent().await.unwrap();
+	let mint = Keypair::new();
+	let instructions = [
+		system_instruction::create_account(
+			&ctx.payer.pubkey(),
+			&mint.pubkey(),
+			rent.minimum_balance(spl_token::state::Mint::LEN),
+			spl_token::state::Mint::LEN as u64,
+			&spl_token::ID,
+		),
+		spl_token::instruction::initialize_mint(&spl_token::ID, &mint.pubkey(), &authority.pubkey(), None, 0).unwrap(),
+	];
+	send(ctx, &instructions, &[&mint]).await.unwrap();
+
+	let mut accounts = Vec::new();
+	for owner in owners {
+		let account = Keypair::new();
+		let instructions = [
+			system_instruction::create_account(
+				&ctx.payer.pubkey(),
+				&account.pubkey(),
+				rent.minimum_balance(spl_token::state::Account::LEN),
+				spl_token::state::Account::LEN as u64,
+				&spl_token::ID,
+			),
+			spl_token::instruction::initialize_account(&spl_token::ID, &account.pubkey(), &mint.pubkey(), &owner.pubkey()).unwrap(),
+			spl_token::instruction::mint_to(&spl_token::ID, &mint.pubkey(), &account.pubkey(), &authority.pubkey(), &[], amount).unwrap(),
+		];
+		send(ctx, &instructions, &[&account, authority]).await.unwrap();
+		accounts.push(account.pubkey());
+	}
+
+	(mint.pubkey(), accounts)
+}
+
+async fn add_token_item(
+	ctx: &mut ProgramTestContext,
+	list: &TestList,
+	user: &Keypair,
+	mint: &Pubkey,
+	user_token: &Pubkey,
+	amount: u64,
+) -> Pubkey {
+	let item = next_item(ctx, list).await;
+	let page_no = next_page(ctx, list).await;
+	let escrow = escrow_accounts(&item, &[]);
+	let ix = Instruction {
+		program_id: todos::ID,
+		accounts: todos::accounts::AddToken {
+			list: list.address,
+			list_owner: list.owner,
+			page: page_address(&list.address, page_no),
+			item,
+			mint: *mint,
+			escrow: escrow[0].pubkey,
+			escrow_authority: escrow[1].pubkey,
+			user_token: *user_token,
+			token_program: spl_token::ID,
+			system_program: system_program::ID,
+			rent: sysvar::rent::ID,
+			user: user.pubkey(),
+			config: config_address(),
+		}
+		.to_account_metas(None),
+		data: todos::instruction::AddToken {
+			_list_name: list.name.clone(),
+			item_name: "An item".to_string(),
+			amount,
+			deadline: None,
+			uri: None,
+			content_hash: [0; 32],
+			page_no,
+		}
+		.data(),
+	};
+	send(ctx, &[ix], &[user]).await.unwrap();
+	item
+}
+
+/// Writes a list as the baseline program laid it out, a Borsh `TodoList` holding its items in `lines`,
+/// at the address it derived from the truncated name seed.
+fn set_legacy_list(ctx: &mut ProgramTestContext, owner: &Pubkey, name: &str, lines: &[Pubkey]) -> TestList {
+	let capacity: u16 = 16;
+	let (address, bump) = list_address(owner, name, SeedScheme::Truncated);
+
+	let mut data = hash(b"account:TodoList").to_bytes()[..8].to_vec();
+	data.extend_from_slice(owner.as_ref());
+	data.extend_from_slice(&capacity.to_le_bytes());
+	data.push(bump);
+	data.extend_from_slice(&(name.len() as u32).to_le_bytes());
+	data.extend_from_slice(name.as_bytes());
+	data.extend_from_slice(&(lines.len() as u32).to_le_bytes());
+	for line in lines {
+		data.extend_from_slice(line.as_ref());
+	}
+	// The baseline allocated room for `capacity` lines up front
+	data.resize(8 + 32 + 2 + 1 + 4 + name.len() + 4 + capacity as usize * 32, 0);
+
+	ctx.set_account(&address, &program_account(Rent::default().minimum_balance(data.len()), data));
+	TestList {
+		address,
+		owner: *owner,
+		name: name.to_string(),
+	}
+}
+
+/// Writes an item as the baseline program laid it out, a Borsh `ListItem` of its creator, two finish flags and its name.
+/// Its lamports are the whole bounty, the baseline took the rent out of it.
+fn set_legacy_item(ctx: &mut ProgramTestContext, creator: &Pubkey, name: &str, bounty: u64) -> Pubkey {
+	let address = Pubkey::new_unique();
+
+	let mut data = hash(b"account:ListItem").to_bytes()[..8].to_vec();
+	data.extend_from_slice(creator.as_ref());
+	data.extend_from_slice(&[0, 0]);
+	data.extend_from_slice(&(name.len() as u32).to_le_bytes());
+	data.extend_from_slice(name.as_bytes());
+
+	ctx.set_account(&address, &program_account(bounty, data));
+	address
+}
+
+fn program_account(lamports: u64, data: Vec<u8>) -> AccountSharedData {
+	AccountSharedData::from(solana_sdk::account::Account {
+		lamports,
+		data,
+		owner: todos::ID,
+		executable: false,
+		rent_epoch: 0,
+	})
+}
+
+async fn fetch_legacy_lines(ctx: &mut ProgramTestContext, list: &Pubkey) -> Vec<Pubkey> {
+	let account = ctx.banks_client.get_account(*list).await.unwrap().expect("legacy list exists");
+	todos::LegacyTodoList::deserialize(&mut &account.data[8..]).unwrap().lines
+}
+
+fn reclaim_legacy_item_ix(list: &TestList, item: &Pubkey, item_creator: &Pubkey) -> Instruction {
+	Instruction {
+		program_id: todos::ID,
+		accounts: todos::accounts::ReclaimLegacyItem {
+			list: list.address,
+			item: *item,
+			item_creator: *item_creator,
+			config: config_address(),
+		}
+		.to_account_metas(None),
+		data: todos::instruction::ReclaimLegacyItem {}.data(),
+	}
+}
+
+fn close_legacy_list_ix(list: &TestList, list_owner: &Pubkey) -> Instruction {
+	Instruction {
+		program_id: todos::ID,
+		accounts: todos::accounts::CloseLegacyList {
+			list: list.address,
+			list_owner: *list_owner,
+			config: config_address(),
+		}
+		.to_account_metas(None),
+		data: todos::instruction::CloseLegacyList {}.data(),
+	}
+}
+// <== }
+
+// { == Create lists ==>
+#[tokio::test]
+async fn can_create_a_list() {
+	let mut ctx = setup().await;
+	let owner = create_user(&mut ctx).await;
+
+	let list = create_list(&mut ctx, &owner, "A list", Some(16), None, None).await;
+	let (data, lines) = fetch_list(&mut ctx, &list.address).await;
+
+	assert_eq!(data.list_owner, owner.pubkey(), "List owner is set");
+	assert_eq!(data.bump, list_address(&owner.pubkey(), "A list", SeedScheme::Hashed).1, "Canonical bump is stored");
+	assert_eq!(data.name(), "A list", "List name is set");
+	assert!(lines.is_empty(), "List has no items");
+	assert_eq!(data.arbiter, Pubkey::default(), "List has no arbiter");
+}
+
+#[tokio::test]
+async fn can_create_another_list_for_a_user_with_an_active_list() {
+	let mut ctx = setup().await;
+	let owner = create_user(&mut ctx).await;
+
+	create_list(&mut ctx, &owner, "A list", Some(16), None, None).await;
+	let list = create_list(&mut ctx, &owner, "Another list", Some(16), None, None).await;
+	let (data, lines) = fetch_list(&mut ctx, &list.address).await;
+
+	assert_eq!(data.name(), "Another list", "List name is set");
+	assert!(lines.is_empty(), "List has no items");
+}
+
+#[tokio::test]
+async fn can_create_lists_whose_names_share_the_first_32_bytes() {
+	let mut ctx = setup().await;
+	let owner = create_user(&mut ctx).await;
+
+	let q1 = create_list(&mut ctx, &owner, "Quarterly infrastructure roadmap - Q1", Some(16), None, None).await;
+	let q2 = create_list(&mut ctx, &owner, "Quarterly infrastructure roadmap - Q2", Some(16), None, None).await;
+
+	assert_ne!(q1.address, q2.address, "Hashed seeds use the full name");
+	let (data, _) = fetch_list(&mut ctx, &q2.address).await;
+	assert_eq!(data.name(), "Quarterly infrastructure roadmap - Q2", "List name is set");
+}
+
+#[tokio::test]
+async fn can_create_a_list_with_the_legacy_truncated_seed() {
+	let mut ctx = setup().await;
+	let owner = create_user(&mut ctx).await;
+
+	let (ix, address) = new_list_ix(&owner.pubkey(), "A legacy list", Some(16), None, None, SeedScheme::Truncated);
+	send(&mut ctx, &[ix], &[&owner]).await.unwrap();
+	let list = TestList {
+		address,
+		owner: owner.pubkey(),
+		name: "A legacy list".to_string(),
+	};
+
+	let (data, _) = fetch_list(&mut ctx, &list.address).await;
+	assert!(data.seed_scheme() == SeedScheme::Truncated, "Seed scheme is stored");
+	add_item(&mut ctx, &list, &owner, "An item", LPS, None).await.unwrap();
+}
+
+#[tokio::test]
+async fn cannot_create_a_list_with_an_invalid_name() {
+	let mut ctx = setup().await;
+	let owner = create_user(&mut ctx).await;
+
+	let (ix, _) = new_list_ix(&owner.pubkey(), "", Some(16), None, None, SeedScheme::Hashed);
+	let result = send(&mut ctx, &[ix], &[&owner]).await;
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::ListNameEmpty));
+
+	let (ix, _) = new_list_ix(&owner.pubkey(), &"a".repeat(65), Some(16), None, None, SeedScheme::Hashed);
+	let result = send(&mut ctx, &[ix], &[&owner]).await;
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::ListNameTooLong));
+
+	// "ä" takes two bytes, the 32 byte seed would end in the middle of it
+	let name = format!("{}ä", "a".repeat(31));
+	let (ix, _) = new_list_ix(&owner.pubkey(), &name, Some(16), None, None, SeedScheme::Truncated);
+	let result = send(&mut ctx, &[ix], &[&owner]).await;
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::ListNameSplitsCharacter));
+}
+
+#[tokio::test]
+async fn cannot_create_a_list_with_a_review_window_but_no_arbiter() {
+	let mut ctx = setup().await;
+	let owner = create_user(&mut ctx).await;
+
+	let (ix, _) = new_list_ix(&owner.pubkey(), "list", Some(16), None, Some(3600), SeedScheme::Hashed);
+	let result = send(&mut ctx, &[ix], &[&owner]).await;
+
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::ReviewWindowNeedsArbiter));
+}
+
+#[tokio::test]
+async fn cannot_create_a_list_with_the_owner_as_arbiter() {
+	let mut ctx = setup().await;
+	let owner = create_user(&mut ctx).await;
+
+	let (ix, _) = new_list_ix(&owner.pubkey(), "list", Some(16), Some(owner.pubkey()), None, SeedScheme::Hashed);
+	let result = send(&mut ctx, &[ix], &[&owner]).await;
+
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::OwnerIsArbiter));
+}
+// <== }
+
+// { == Add items ==>
+#[tokio::test]
+async fn can_add_items_from_different_users() {
+	let mut ctx = setup().await;
+	let [owner, adder, other_user] = create_users(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), None, None).await;
+
+	let adder_starting_balance = balance(&mut ctx, &adder.pubkey()).await;
+	let item = add_item(&mut ctx, &list, &adder, "Do something", LPS, None).await.unwrap();
+
+	let data: ListItem = fetch(&mut ctx, &item).await;
+	assert_eq!(data.creator, adder.pubkey(), "Item marked with creator");
+	assert!(!data.creator_finished, "creator_finished is false");
+	assert!(!data.list_owner_finished, "list_owner_finished is false");
+	assert_eq!(data.name, "Do something", "Name is set");
+	assert_eq!(data.bounty_mint, None, "Bounty is paid in lamports");
+	assert_eq!(data.bounty_amount, LPS, "Bounty amount is recorded");
+	assert_eq!(balance(&mut ctx, &item).await, LPS, "Item account balance");
+	// The first item opens the first page, its adder pays the rent of the page
+	let page_rent = balance(&mut ctx, &first_page(&list)).await;
+	assert_eq!(
+		adder_starting_balance - balance(&mut ctx, &adder.pubkey()).await,
+		LPS + page_rent,
+		"Number of lamports removed from adder is equal to bounty and page rent"
+	);
+
+	let item_two = add_item(&mut ctx, &list, &other_user, "Do something more", LPS, None).await.unwrap();
+	let item_three = add_item(&mut ctx, &list, &adder, "Another item", LPS, None).await.unwrap();
+
+	let (data, lines) = fetch_list(&mut ctx, &list.address).await;
+	assert_eq!(lines, vec![item, item_two, item_three], "Items are added");
+	assert_eq!(data.item_count, 3, "Item count is incremented");
+}
+
+#[tokio::test]
+async fn cannot_add_items_when_the_list_is_full() {
+	let mut ctx = setup().await;
+	let owner = create_user(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(4), None, None).await;
+
+	for i in 0..4 {
+		add_item(&mut ctx, &list, &owner, &format!("Filler item {}", i), LPS, None).await.unwrap();
+	}
+
+	let adder_starting_balance = balance(&mut ctx, &owner.pubkey()).await;
+	let result = add_item(&mut ctx, &list, &owner, "Overflow item", LPS, None).await.map(|_| ());
+
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::ListFull));
+	assert_eq!(balance(&mut ctx, &owner.pubkey()).await, adder_starting_balance, "Adder balance is unchanged");
+}
+
+#[tokio::test]
+async fn cannot_use_a_bounty_smaller_than_the_rent_exempt_amount() {
+	let mut ctx = setup().await;
+	let owner = create_user(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), None, None).await;
+
+	let adder_starting_balance = balance(&mut ctx, &owner.pubkey()).await;
+	let result = add_item(&mut ctx, &list, &owner, "Small bounty item", 10, None).await.map(|_| ());
+
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::BountyTooSmall));
+	assert_eq!(balance(&mut ctx, &owner.pubkey()).await, adder_starting_balance, "Adder balance is unchanged");
+}
+// <== }
+
+// { == Cancel items ==>
+#[tokio::test]
+async fn can_cancel_item_list_owner() {
+	let mut ctx = setup().await;
+	let [owner, adder] = create_users(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), None, None).await;
+	let item = add_item(&mut ctx, &list, &adder, "An item", LPS, None).await.unwrap();
+
+	let adder_balance_after_add = balance(&mut ctx, &adder.pubkey()).await;
+	send(&mut ctx, &[cancel_ix(&list, &item, &adder.pubkey(), &owner.pubkey(), vec![])], &[&owner])
+		.await
+		.unwrap();
+
+	assert_eq!(balance(&mut ctx, &adder.pubkey()).await, adder_balance_after_add + LPS, "Cancel returns bounty to adder");
+	let (_, lines) = fetch_list(&mut ctx, &list.address).await;
+	assert!(lines.is_empty(), "Cancel removes item from list");
+}
+
+#[tokio::test]
+async fn can_cancel_item_last_item_moves_into_the_freed_slot() {
+	let mut ctx = setup().await;
+	let owner = create_user(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), None, None).await;
+	let first = add_item(&mut ctx, &list, &owner, "First item", LPS, None).await.unwrap();
+	let second = add_item(&mut ctx, &list, &owner, "Second item", LPS, None).await.unwrap();
+	let third = add_item(&mut ctx, &list, &owner, "Third item", LPS, None).await.unwrap();
+	let data: ListItem = fetch(&mut ctx, &third).await;
+	assert_eq!(data.slot, 2, "Item stores its slot");
+
+	let ix = moving(cancel_ix(&list, &first, &owner.pubkey(), &owner.pubkey(), vec![]), &third);
+	send(&mut ctx, &[ix], &[&owner]).await.unwrap();
+
+	let (_, lines) = fetch_list(&mut ctx, &list.address).await;
+	assert_eq!(lines, vec![third, second], "Last item takes the slot of the cancelled one");
+	let data: ListItem = fetch(&mut ctx, &third).await;
+	assert_eq!(data.slot, 0, "Moved item stores its new slot");
+
+	let ix = moving(cancel_ix(&list, &third, &owner.pubkey(), &owner.pubkey(), vec![]), &second);
+	send(&mut ctx, &[ix], &[&owner]).await.unwrap();
+
+	let (_, lines) = fetch_list(&mut ctx, &list.address).await;
+	assert_eq!(lines, vec![second], "Moved item is removed from its new slot");
+}
+
+#[tokio::test]
+async fn cannot_cancel_item_wrong_moved_item() {
+	let mut ctx = setup().await;
+	let owner = create_user(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), None, None).await;
+	let first = add_item(&mut ctx, &list, &owner, "First item", LPS, None).await.unwrap();
+	let second = add_item(&mut ctx, &list, &owner, "Second item", LPS, None).await.unwrap();
+	add_item(&mut ctx, &list, &owner, "Third item", LPS, None).await.unwrap();
+
+	let ix = moving(cancel_ix(&list, &first, &owner.pubkey(), &owner.pubkey(), vec![]), &second);
+	let result = send(&mut ctx, &[ix], &[&owner]).await;
+
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::WrongMovedItem));
+}
+
+#[tokio::test]
+async fn can_cancel_item_item_creator() {
+	let mut ctx = setup().await;
+	let [owner, adder] = create_users(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), None, None).await;
+	let item = add_item(&mut ctx, &list, &adder, "An item", LPS, None).await.unwrap();
+
+	let adder_balance_after_add = balance(&mut ctx, &adder.pubkey()).await;
+	send(&mut ctx, &[cancel_ix(&list, &item, &adder.pubkey(), &adder.pubkey(), vec![])], &[&adder])
+		.await
+		.unwrap();
+
+	assert_eq!(balance(&mut ctx, &adder.pubkey()).await, adder_balance_after_add + LPS, "Cancel returns bounty to adder");
+	let (_, lines) = fetch_list(&mut ctx, &list.address).await;
+	assert!(lines.is_empty(), "Cancel removes item from list");
+}
+
+#[tokio::test]
+async fn cannot_cancel_item_item_creator_after_the_owner_finished() {
+	let mut ctx = setup().await;
+	let [owner, adder, arbiter] = create_users(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), Some(arbiter.pubkey()), None).await;
+	let item = add_item(&mut ctx, &list, &adder, "An item", LPS, None).await.unwrap();
+	send(&mut ctx, &[finish_ix(&list, &owner.pubkey(), &item, &owner.pubkey(), vec![])], &[&owner])
+		.await
+		.unwrap();
+
+	let result = send(&mut ctx, &[cancel_ix(&list, &item, &adder.pubkey(), &adder.pubkey(), vec![])], &[&adder]).await;
+
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::OwnerAlreadyFinished));
+	assert_eq!(balance(&mut ctx, &item).await, LPS, "Item balance is unchanged after failed cancel");
+
+	let adder_balance = balance(&mut ctx, &adder.pubkey()).await;
+	send(&mut ctx, &[cancel_ix(&list, &item, &adder.pubkey(), &owner.pubkey(), vec![])], &[&owner])
+		.await
+		.unwrap();
+	assert_eq!(balance(&mut ctx, &adder.pubkey()).await, adder_balance + LPS, "List owner may still cancel");
+}
+
+#[tokio::test]
+async fn cannot_expire_item_after_the_owner_finished() {
+	let mut ctx = setup().await;
+	let [owner, adder, arbiter] = create_users(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), Some(arbiter.pubkey()), None).await;
+	let now = unix_timestamp(&mut ctx).await;
+	let item = add_item(&mut ctx, &list, &adder, "An item", LPS, Some(now + 60)).await.unwrap();
+	send(&mut ctx, &[finish_ix(&list, &owner.pubkey(), &item, &owner.pubkey(), vec![])], &[&owner])
+		.await
+		.unwrap();
+
+	set_unix_timestamp(&mut ctx, now + 60).await;
+	let result = send(&mut ctx, &[expire_ix(&list, &item, &adder.pubkey())], &[]).await;
+
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::OwnerAlreadyFinished));
+	assert_eq!(balance(&mut ctx, &item).await, LPS, "Item balance is unchanged after failed expire");
+}
+
+#[tokio::test]
+async fn can_cancel_item_item_creator_after_the_owner_finished_in_a_list_without_arbiter() {
+	let mut ctx = setup().await;
+	let [owner, adder] = create_users(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), None, None).await;
+	let item = add_item(&mut ctx, &list, &adder, "An item", LPS, None).await.unwrap();
+	send(&mut ctx, &[finish_ix(&list, &owner.pubkey(), &item, &owner.pubkey(), vec![])], &[&owner])
+		.await
+		.unwrap();
+
+	let adder_balance = balance(&mut ctx, &adder.pubkey()).await;
+	send(&mut ctx, &[cancel_ix(&list, &item, &adder.pubkey(), &adder.pubkey(), vec![])], &[&adder])
+		.await
+		.unwrap();
+
+	assert_eq!(balance(&mut ctx, &adder.pubkey()).await, adder_balance + LPS, "Without arbiter the creator may still cancel");
+}
+
+#[tokio::test]
+async fn can_expire_item_after_the_owner_finished_in_a_list_without_arbiter() {
+	let mut ctx = setup().await;
+	let [owner, adder] = create_users(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), None, None).await;
+	let now = unix_timestamp(&mut ctx).await;
+	let item = add_item(&mut ctx, &list, &adder, "An item", LPS, Some(now + 60)).await.unwrap();
+	send(&mut ctx, &[finish_ix(&list, &owner.pubkey(), &item, &owner.pubkey(), vec![])], &[&owner])
+		.await
+		.unwrap();
+
+	set_unix_timestamp(&mut ctx, now + 60).await;
+	let adder_balance = balance(&mut ctx, &adder.pubkey()).await;
+	send(&mut ctx, &[expire_ix(&list, &item, &adder.pubkey())], &[]).await.unwrap();
+
+	assert_eq!(balance(&mut ctx, &adder.pubkey()).await, adder_balance + LPS, "Without arbiter the deadline still refunds the creator");
+}
+
+#[tokio::test]
+async fn cannot_cancel_item_other_user() {
+	let mut ctx = setup().await;
+	let [owner, adder, other_user] = create_users(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), None, None).await;
+	let item = add_item(&mut ctx, &list, &adder, "An item", LPS, None).await.unwrap();
+
+	let adder_balance_after_add = balance(&mut ctx, &adder.pubkey()).await;
+	let result = send(&mut ctx, &[cancel_ix(&list, &item, &adder.pubkey(), &other_user.pubkey(), vec![])], &[&other_user]).await;
+
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::CancelPermissions));
+	assert_eq!(balance(&mut ctx, &adder.pubkey()).await, adder_balance_after_add, "Failed cancel does not change adder balance");
+	assert_eq!(balance(&mut ctx, &item).await, LPS, "Item balance is unchanged after failed cancel");
+	let (_, lines) = fetch_list(&mut ctx, &list.address).await;
+	assert_eq!(lines, vec![item], "Item is still in list after failed cancel");
+}
+
+#[tokio::test]
+async fn cannot_cancel_item_item_creator_with_wrong_key() {
+	let mut ctx = setup().await;
+	let [owner, adder] = create_users(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), None, None).await;
+	let item = add_item(&mut ctx, &list, &adder, "An item", LPS, None).await.unwrap();
+
+	let result = send(&mut ctx, &[cancel_ix(&list, &item, &owner.pubkey(), &owner.pubkey(), vec![])], &[&owner]).await;
+
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::WrongItemCreator));
+}
+
+#[tokio::test]
+async fn cannot_cancel_item_in_other_list() {
+	let mut ctx = setup().await;
+	let [owner, adder] = create_users(&mut ctx).await;
+	let list1 = create_list(&mut ctx, &owner, "list1", Some(16), None, None).await;
+	let list2 = create_list(&mut ctx, &owner, "list2", Some(16), None, None).await;
+	let item = add_item(&mut ctx, &list1, &adder, "An item", LPS, None).await.unwrap();
+	add_item(&mut ctx, &list2, &adder, "An item", LPS, None).await.unwrap();
+
+	let result = send(&mut ctx, &[cancel_ix(&list2, &item, &adder.pubkey(), &owner.pubkey(), vec![])], &[&owner]).await;
+
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::ItemNotFound));
+}
+// <== }
+
+// { == Finish ==>
+#[tokio::test]
+async fn can_finish_items_first_owner_then_item_creator() {
+	let mut ctx = setup().await;
+	let [owner, adder] = create_users(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), None, None).await;
+	let owner_initial = balance(&mut ctx, &owner.pubkey()).await;
+	let item = add_item(&mut ctx, &list, &adder, "An item", 5 * LPS, None).await.unwrap();
+
+	send(&mut ctx, &[finish_ix(&list, &owner.pubkey(), &item, &owner.pubkey(), vec![])], &[&owner])
+		.await
+		.unwrap();
+
+	let data: ListItem = fetch(&mut ctx, &item).await;
+	assert!(!data.creator_finished, "Creator finish is false after owner calls finish");
+	assert!(data.list_owner_finished, "Owner finish flag gets set after owner calls finish");
+	assert_eq!(balance(&mut ctx, &item).await, 5 * LPS, "Bounty remains on item after one finish call");
+
+	send(&mut ctx, &[finish_ix(&list, &owner.pubkey(), &item, &adder.pubkey(), vec![])], &[&adder])
+		.await
+		.unwrap();
+
+	let (_, lines) = fetch_list(&mut ctx, &list.address).await;
+	assert!(lines.is_empty(), "Item removed from list after both finish");
+	assert_eq!(balance(&mut ctx, &item).await, 0, "Item is closed");
+	assert_eq!(balance(&mut ctx, &owner.pubkey()).await, owner_initial + 5 * LPS, "Bounty transferred to owner");
+}
+
+#[tokio::test]
+async fn can_finish_items_first_item_creator_then_list_owner() {
+	let mut ctx = setup().await;
+	let [owner, adder] = create_users(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), None, None).await;
+	let owner_initial = balance(&mut ctx, &owner.pubkey()).await;
+	let item = add_item(&mut ctx, &list, &adder, "An item", 5 * LPS, None).await.unwrap();
+
+	send(&mut ctx, &[finish_ix(&list, &owner.pubkey(), &item, &adder.pubkey(), vec![])], &[&adder])
+		.await
+		.unwrap();
+
+	let data: ListItem = fetch(&mut ctx, &item).await;
+	assert!(data.creator_finished, "Creator finish is true after creator calls finish");
+	assert!(!data.list_owner_finished, "Owner finish flag is false after creator calls finish");
+
+	send(&mut ctx, &[finish_ix(&list, &owner.pubkey(), &item, &owner.pubkey(), vec![])], &[&owner])
+		.await
+		.unwrap();
+
+	assert_eq!(balance(&mut ctx, &item).await, 0, "Item is closed");
+	assert_eq!(balance(&mut ctx, &owner.pubkey()).await, owner_initial + 5 * LPS, "Bounty transferred to owner");
+}
+
+#[tokio::test]
+async fn cannot_finish_items_other_user() {
+	let mut ctx = setup().await;
+	let [owner, adder, other_user] = create_users(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), None, None).await;
+	let item = add_item(&mut ctx, &list, &adder, "An item", 5 * LPS, None).await.unwrap();
+
+	let result = send(&mut ctx, &[finish_ix(&list, &owner.pubkey(), &item, &other_user.pubkey(), vec![])], &[&other_user]).await;
+
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::FinishPermissions));
+	assert_eq!(balance(&mut ctx, &item).await, 5 * LPS, "Item balance did not change");
+}
+
+#[tokio::test]
+async fn cannot_finish_item_in_other_list() {
+	let mut ctx = setup().await;
+	let [owner, adder] = create_users(&mut ctx).await;
+	let list1 = create_list(&mut ctx, &owner, "list1", Some(16), None, None).await;
+	let list2 = create_list(&mut ctx, &owner, "list2", Some(16), None, None).await;
+	let item = add_item(&mut ctx, &list1, &adder, "An item", 5 * LPS, None).await.unwrap();
+	// The adder also pays the rent of the first page of both lists
+	add_item(&mut ctx, &list2, &adder, "An item", LPS, None).await.unwrap();
+
+	let result = send(&mut ctx, &[finish_ix(&list2, &owner.pubkey(), &item, &adder.pubkey(), vec![])], &[&adder]).await;
+
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::ItemNotFound));
+	assert_eq!(balance(&mut ctx, &item).await, 5 * LPS, "Item balance did not change");
+}
+
+#[tokio::test]
+async fn cannot_finish_item_with_wrong_list_owner() {
+	let mut ctx = setup().await;
+	let [owner, adder] = create_users(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list1", Some(16), None, None).await;
+	let item = add_item(&mut ctx, &list, &adder, "An item", 5 * LPS, None).await.unwrap();
+
+	let result = send(&mut ctx, &[finish_ix(&list, &adder.pubkey(), &item, &owner.pubkey(), vec![])], &[&owner]).await;
+
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::WrongListOwner));
+	assert_eq!(balance(&mut ctx, &item).await, 5 * LPS, "Item balance did not change");
+}
+
+#[tokio::test]
+async fn cannot_finish_an_already_finished_item() {
+	let mut ctx = setup().await;
+	let [owner, adder] = create_users(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), None, None).await;
+	let owner_initial = balance(&mut ctx, &owner.pubkey()).await;
+	let item = add_item(&mut ctx, &list, &adder, "An item", 5 * LPS, None).await.unwrap();
+
+	send(&mut ctx, &[finish_ix(&list, &owner.pubkey(), &item, &owner.pubkey(), vec![])], &[&owner])
+		.await
+		.unwrap();
+	send(&mut ctx, &[finish_ix(&list, &owner.pubkey(), &item, &adder.pubkey(), vec![])], &[&adder])
+		.await
+		.unwrap();
+
+	next_slot(&mut ctx).await;
+	let result = send(&mut ctx, &[finish_ix(&list, &owner.pubkey(), &item, &owner.pubkey(), vec![])], &[&owner]).await;
+
+	assert_eq!(error_code(result), anchor_error(anchor_lang::error::ErrorCode::AccountNotInitialized));
+	assert_eq!(balance(&mut ctx, &owner.pubkey()).await, owner_initial + 5 * LPS, "Bounty transferred to owner just once");
+}
+
+#[tokio::test]
+async fn can_unfinish_an_item_each_side_clears_only_their_mark() {
+	let mut ctx = setup().await;
+	let [owner, adder, arbiter] = create_users(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), Some(arbiter.pubkey()), Some(3600)).await;
+	let item = add_item(&mut ctx, &list, &adder, "An item", LPS, None).await.unwrap();
+
+	send(&mut ctx, &[finish_ix(&list, &owner.pubkey(), &item, &owner.pubkey(), vec![])], &[&owner])
+		.await
+		.unwrap();
+	send(&mut ctx, &[unfinish_ix(&list, &item, &owner.pubkey())], &[&owner]).await.unwrap();
+
+	let data: ListItem = fetch(&mut ctx, &item).await;
+	assert!(!data.list_owner_finished, "Owner finish flag is cleared");
+	assert_eq!(data.owner_finished_at, None, "Review window is reset");
+
+	send(&mut ctx, &[finish_ix(&list, &owner.pubkey(), &item, &adder.pubkey(), vec![])], &[&adder])
+		.await
+		.unwrap();
+	let result = send(&mut ctx, &[unfinish_ix(&list, &item, &owner.pubkey())], &[&owner]).await;
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::NotMarkedFinished));
+
+	let data: ListItem = fetch(&mut ctx, &item).await;
+	assert!(data.creator_finished, "Owner cannot clear the creator's mark");
+
+	send(&mut ctx, &[unfinish_ix(&list, &item, &adder.pubkey())], &[&adder]).await.unwrap();
+	let data: ListItem = fetch(&mut ctx, &item).await;
+	assert!(!data.creator_finished, "Creator finish flag is cleared");
+}
+
+#[tokio::test]
+async fn cannot_unfinish_an_item_other_user() {
+	let mut ctx = setup().await;
+	let [owner, adder, other_user] = create_users(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), None, None).await;
+	let item = add_item(&mut ctx, &list, &adder, "An item", LPS, None).await.unwrap();
+	send(&mut ctx, &[finish_ix(&list, &owner.pubkey(), &item, &owner.pubkey(), vec![])], &[&owner])
+		.await
+		.unwrap();
+
+	let result = send(&mut ctx, &[unfinish_ix(&list, &item, &other_user.pubkey())], &[&other_user]).await;
+
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::FinishPermissions));
+}
+// <== }
+
+// { == Token bounties ==>
+#[tokio::test]
+async fn can_cancel_a_token_item_tokens_return_to_the_item_creator() {
+	let mut ctx = setup().await;
+	let [owner, adder] = create_users(&mut ctx).await;
+	let (mint, accounts) = create_token_accounts(&mut ctx, &adder, &[&adder], 1000).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), None, None).await;
+	let item = add_token_item(&mut ctx, &list, &adder, &mint, &accounts[0], 400).await;
+
+	let data: ListItem = fetch(&mut ctx, &item).await;
+	assert_eq!(data.bounty_mint, Some(mint), "Item records the bounty mint");
+	assert_eq!(data.bounty_amount, 400, "Item records the bounty amount");
+	assert_eq!(token_balance(&mut ctx, &accounts[0]).await, 600, "Bounty is removed from adder");
+
+	let remaining = escrow_accounts(&item, &[accounts[0]]);
+	let escrow = remaining[0].pubkey;
+	send(&mut ctx, &[cancel_ix(&list, &item, &adder.pubkey(), &owner.pubkey(), remaining)], &[&owner])
+		.await
+		.unwrap();
+
+	assert_eq!(token_balance(&mut ctx, &accounts[0]).await, 1000, "Cancel returns tokens to adder");
+	assert_eq!(balance(&mut ctx, &escrow).await, 0, "Escrow is closed");
+}
+
+#[tokio::test]
+async fn cannot_cancel_a_token_item_without_the_escrow_accounts() {
+	let mut ctx = setup().await;
+	let [owner, adder] = create_users(&mut ctx).await;
+	let (mint, accounts) = create_token_accounts(&mut ctx, &adder, &[&adder], 1000).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), None, None).await;
+	let item = add_token_item(&mut ctx, &list, &adder, &mint, &accounts[0], 400).await;
+
+	let result = send(&mut ctx, &[cancel_ix(&list, &item, &adder.pubkey(), &adder.pubkey(), vec![])], &[&adder]).await;
+
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::MissingEscrowAccounts));
+}
+
+#[tokio::test]
+async fn can_finish_a_token_item_tokens_are_paid_to_the_list_owner() {
+	let mut ctx = setup().await;
+	let [owner, adder] = create_users(&mut ctx).await;
+	let (mint, accounts) = create_token_accounts(&mut ctx, &adder, &[&adder, &owner], 1000).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), None, None).await;
+	let item = add_token_item(&mut ctx, &list, &adder, &mint, &accounts[0], 400).await;
+
+	send(&mut ctx, &[finish_ix(&list, &owner.pubkey(), &item, &owner.pubkey(), vec![])], &[&owner])
+		.await
+		.unwrap();
+
+	let wrong_destination = escrow_accounts(&item, &[accounts[0]]);
+	let result = send(&mut ctx, &[finish_ix(&list, &owner.pubkey(), &item, &adder.pubkey(), wrong_destination)], &[&adder]).await;
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::WrongTokenAccount));
+
+	let remaining = escrow_accounts(&item, &[accounts[1]]);
+	send(&mut ctx, &[finish_ix(&list, &owner.pubkey(), &item, &adder.pubkey(), remaining)], &[&adder])
+		.await
+		.unwrap();
+
+	assert_eq!(token_balance(&mut ctx, &accounts[1]).await, 1400, "Bounty transferred to owner");
+}
+// <== }
+
+// { == Deadlines ==>
+async fn set_unix_timestamp(ctx: &mut ProgramTestContext, unix_timestamp: i64) {
+	let mut clock: Clock = ctx.banks_client.get_sysvar().await.unwrap();
+	clock.unix_timestamp = unix_timestamp;
+	ctx.set_sysvar(&clock);
+}
+
+async fn unix_timestamp(ctx: &mut ProgramTestContext) -> i64 {
+	let clock: Clock = ctx.banks_client.get_sysvar().await.unwrap();
+	clock.unix_timestamp
+}
+
+#[tokio::test]
+async fn cannot_add_an_item_with_a_deadline_in_the_past() {
+	let mut ctx = setup().await;
+	let [owner, adder] = create_users(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), None, None).await;
+	let now = unix_timestamp(&mut ctx).await;
+
+	let result = add_item(&mut ctx, &list, &adder, "An item", LPS, Some(now - 60)).await.map(|_| ());
+
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::DeadlineInPast));
+}
+
+#[tokio::test]
+async fn cannot_expire_an_item_without_a_deadline() {
+	let mut ctx = setup().await;
+	let [owner, adder] = create_users(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), None, None).await;
+	let item = add_item(&mut ctx, &list, &adder, "An item", LPS, None).await.unwrap();
+
+	let result = send(&mut ctx, &[expire_ix(&list, &item, &adder.pubkey())], &[]).await;
+
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::NoDeadline));
+}
+
+#[tokio::test]
+async fn cannot_expire_an_item_before_its_deadline() {
+	let mut ctx = setup().await;
+	let [owner, adder] = create_users(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), None, None).await;
+	let now = unix_timestamp(&mut ctx).await;
+	let item = add_item(&mut ctx, &list, &adder, "An item", LPS, Some(now + 3600)).await.unwrap();
+
+	let result = send(&mut ctx, &[expire_ix(&list, &item, &adder.pubkey())], &[]).await;
+
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::DeadlineNotReached));
+	assert_eq!(balance(&mut ctx, &item).await, LPS, "Item balance is unchanged");
+}
+
+#[tokio::test]
+async fn can_expire_an_item_after_its_deadline_bounty_returns_to_the_item_creator() {
+	let mut ctx = setup().await;
+	let [owner, adder] = create_users(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), None, None).await;
+	let now = unix_timestamp(&mut ctx).await;
+	let item = add_item(&mut ctx, &list, &adder, "An item", LPS, Some(now + 3600)).await.unwrap();
+	let adder_balance_after_add = balance(&mut ctx, &adder.pubkey()).await;
+
+	set_unix_timestamp(&mut ctx, now + 3600).await;
+	send(&mut ctx, &[expire_ix(&list, &item, &adder.pubkey())], &[]).await.unwrap();
+
+	assert_eq!(balance(&mut ctx, &adder.pubkey()).await, adder_balance_after_add + LPS, "Expire returns bounty to adder");
+	let (_, lines) = fetch_list(&mut ctx, &list.address).await;
+	assert!(lines.is_empty(), "Expire removes item from list");
+}
+// <== }
+
+// { == Review window ==>
+#[tokio::test]
+async fn cannot_claim_an_item_in_a_list_without_review_window() {
+	let mut ctx = setup().await;
+	let [owner, adder] = create_users(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), None, None).await;
+	let item = add_item(&mut ctx, &list, &adder, "An item", LPS, None).await.unwrap();
+	send(&mut ctx, &[finish_ix(&list, &owner.pubkey(), &item, &owner.pubkey(), vec![])], &[&owner])
+		.await
+		.unwrap();
+
+	let result = send(&mut ctx, &[claim_after_timeout_ix(&list, &item, vec![])], &[&owner]).await;
+
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::NoReviewWindow));
+}
+
+#[tokio::test]
+async fn cannot_claim_an_item_the_owner_did_not_finish() {
+	let mut ctx = setup().await;
+	let [owner, adder, arbiter] = create_users(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), Some(arbiter.pubkey()), Some(3600)).await;
+	let item = add_item(&mut ctx, &list, &adder, "An item", LPS, None).await.unwrap();
+
+	let result = send(&mut ctx, &[claim_after_timeout_ix(&list, &item, vec![])], &[&owner]).await;
+
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::OwnerNotFinished));
+}
+
+#[tokio::test]
+async fn cannot_claim_an_item_before_the_review_window_passed() {
+	let mut ctx = setup().await;
+	let [owner, adder, arbiter] = create_users(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), Some(arbiter.pubkey()), Some(3600)).await;
+	let item = add_item(&mut ctx, &list, &adder, "An item", LPS, None).await.unwrap();
+	send(&mut ctx, &[finish_ix(&list, &owner.pubkey(), &item, &owner.pubkey(), vec![])], &[&owner])
+		.await
+		.unwrap();
+
+	let result = send(&mut ctx, &[claim_after_timeout_ix(&list, &item, vec![])], &[&owner]).await;
+
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::ReviewWindowOpen));
+	assert_eq!(balance(&mut ctx, &item).await, LPS, "Item balance is unchanged");
+}
+
+#[tokio::test]
+async fn can_claim_an_item_after_the_review_window_bounty_is_paid_to_the_list_owner() {
+	let mut ctx = setup().await;
+	let [owner, adder, arbiter] = create_users(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), Some(arbiter.pubkey()), Some(3600)).await;
+	let item = add_item(&mut ctx, &list, &adder, "An item", LPS, None).await.unwrap();
+	send(&mut ctx, &[finish_ix(&list, &owner.pubkey(), &item, &owner.pubkey(), vec![])], &[&owner])
+		.await
+		.unwrap();
+
+	let data: ListItem = fetch(&mut ctx, &item).await;
+	let finished_at = data.owner_finished_at.expect("Owner finish time is recorded");
+	let owner_balance = balance(&mut ctx, &owner.pubkey()).await;
+
+	set_unix_timestamp(&mut ctx, finished_at + 3600).await;
+	send(&mut ctx, &[claim_after_timeout_ix(&list, &item, vec![])], &[&owner]).await.unwrap();
+
+	assert_eq!(balance(&mut ctx, &item).await, 0, "Item is closed");
+	assert_eq!(balance(&mut ctx, &owner.pubkey()).await, owner_balance + LPS, "Bounty transferred to owner");
+	let (_, lines) = fetch_list(&mut ctx, &list.address).await;
+	assert!(lines.is_empty(), "Item removed from list");
+}
+
+#[tokio::test]
+async fn cannot_claim_a_disputed_item() {
+	let mut ctx = setup().await;
+	let [owner, adder, arbiter] = create_users(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), Some(arbiter.pubkey()), Some(3600)).await;
+	let item = add_item(&mut ctx, &list, &adder, "An item", LPS, None).await.unwrap();
+	send(&mut ctx, &[finish_ix(&list, &owner.pubkey(), &item, &owner.pubkey(), vec![])], &[&owner])
+		.await
+		.unwrap();
+	send(&mut ctx, &[open_dispute_ix(&list, &item, &adder.pubkey())], &[&adder]).await.unwrap();
+
+	let now = unix_timestamp(&mut ctx).await;
+	set_unix_timestamp(&mut ctx, now + 3600).await;
+	let result = send(&mut ctx, &[claim_after_timeout_ix(&list, &item, vec![])], &[&owner]).await;
+
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::ItemDisputed));
+}
+// <== }
+
+// { == Disputes ==>
+#[tokio::test]
+async fn cannot_dispute_an_item_in_a_list_without_arbiter() {
+	let mut ctx = setup().await;
+	let [owner, adder] = create_users(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), None, None).await;
+	let item = add_item(&mut ctx, &list, &adder, "An item", LPS, None).await.unwrap();
+
+	let result = send(&mut ctx, &[open_dispute_ix(&list, &item, &owner.pubkey())], &[&owner]).await;
+
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::NoArbiter));
+}
+
+#[tokio::test]
+async fn cannot_cancel_or_finish_a_disputed_item() {
+	let mut ctx = setup().await;
+	let [owner, adder, arbiter] = create_users(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), Some(arbiter.pubkey()), None).await;
+	let item = add_item(&mut ctx, &list, &adder, "An item", LPS, None).await.unwrap();
+
+	send(&mut ctx, &[open_dispute_ix(&list, &item, &owner.pubkey())], &[&owner]).await.unwrap();
+
+	let result = send(&mut ctx, &[cancel_ix(&list, &item, &adder.pubkey(), &adder.pubkey(), vec![])], &[&adder]).await;
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::ItemDisputed));
+
+	let result = send(&mut ctx, &[finish_ix(&list, &owner.pubkey(), &item, &adder.pubkey(), vec![])], &[&adder]).await;
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::ItemDisputed));
+
+	assert_eq!(balance(&mut ctx, &item).await, LPS, "Item balance is unchanged");
+}
+
+#[tokio::test]
+async fn cannot_resolve_a_dispute_other_user() {
+	let mut ctx = setup().await;
+	let [owner, adder, arbiter] = create_users(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), Some(arbiter.pubkey()), None).await;
+	let item = add_item(&mut ctx, &list, &adder, "An item", LPS, None).await.unwrap();
+	send(&mut ctx, &[open_dispute_ix(&list, &item, &adder.pubkey())], &[&adder]).await.unwrap();
+
+	let result = send(
+		&mut ctx,
+		&[resolve_dispute_ix(&list, &item, &adder.pubkey(), &owner.pubkey(), 10_000, vec![])],
+		&[&owner],
+	)
+	.await;
+
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::NotArbiter));
+}
+
+#[tokio::test]
+async fn can_resolve_a_dispute_arbiter_splits_the_bounty() {
+	let mut ctx = setup().await;
+	let [owner, adder, arbiter] = create_users(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), Some(arbiter.pubkey()), None).await;
+	let item = add_item(&mut ctx, &list, &adder, "An item", 2 * LPS, None).await.unwrap();
+	send(&mut ctx, &[open_dispute_ix(&list, &item, &owner.pubkey())], &[&owner]).await.unwrap();
+
+	let owner_balance = balance(&mut ctx, &owner.pubkey()).await;
+	let adder_balance = balance(&mut ctx, &adder.pubkey()).await;
+
+	send(
+		&mut ctx,
+		&[resolve_dispute_ix(&list, &item, &adder.pubkey(), &arbiter.pubkey(), 7_500, vec![])],
+		&[&arbiter],
+	)
+	.await
+	.unwrap();
+
+	assert_eq!(balance(&mut ctx, &item).await, 0, "Item is closed");
+	assert_eq!(balance(&mut ctx, &owner.pubkey()).await, owner_balance + 3 * LPS / 2, "Owner receives their share");
+	assert_eq!(balance(&mut ctx, &adder.pubkey()).await, adder_balance + LPS / 2, "Creator receives the rest");
+}
+
+#[tokio::test]
+async fn can_resolve_a_dispute_over_a_token_bounty() {
+	let mut ctx = setup().await;
+	let [owner, adder, arbiter] = create_users(&mut ctx).await;
+	let (mint, accounts) = create_token_accounts(&mut ctx, &adder, &[&adder, &owner], 1000).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), Some(arbiter.pubkey()), None).await;
+	let item = add_token_item(&mut ctx, &list, &adder, &mint, &accounts[0], 400).await;
+	send(&mut ctx, &[open_dispute_ix(&list, &item, &adder.pubkey())], &[&adder]).await.unwrap();
+
+	let remaining = escrow_accounts(&item, &[accounts[1], accounts[0]]);
+	send(
+		&mut ctx,
+		&[resolve_dispute_ix(&list, &item, &adder.pubkey(), &arbiter.pubkey(), 2_500, remaining)],
+		&[&arbiter],
+	)
+	.await
+	.unwrap();
+
+	assert_eq!(token_balance(&mut ctx, &accounts[1]).await, 1100, "Owner receives their share");
+	assert_eq!(token_balance(&mut ctx, &accounts[0]).await, 900, "Creator receives the rest");
+}
+// <== }
+
+// { == Crowdfunding ==>
+#[tokio::test]
+async fn can_fund_an_existing_item_from_multiple_users() {
+	let mut ctx = setup().await;
+	let [owner, adder, funder] = create_users(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), None, None).await;
+	let item = add_item(&mut ctx, &list, &adder, "An item", LPS, None).await.unwrap();
+
+	send(&mut ctx, &[fund_ix(&list, &item, &funder.pubkey(), LPS)], &[&funder]).await.unwrap();
+	next_slot(&mut ctx).await;
+	send(&mut ctx, &[fund_ix(&list, &item, &funder.pubkey(), LPS)], &[&funder]).await.unwrap();
+	send(&mut ctx, &[fund_ix(&list, &item, &owner.pubkey(), LPS)], &[&owner]).await.unwrap();
+
+	let contribution: Contribution = fetch(&mut ctx, &contribution_address(&item, &funder.pubkey())).await;
+	assert_eq!(contribution.amount, 2 * LPS, "Contributions of a funder add up");
+
+	let data: ListItem = fetch(&mut ctx, &item).await;
+	assert_eq!(data.funders, 2, "Every funder is counted once");
+	assert_eq!(data.funded_amount, 3 * LPS, "Funded amount is recorded");
+	assert_eq!(data.bounty_amount, 4 * LPS, "Bounty includes the funding");
+	assert_eq!(balance(&mut ctx, &item).await, 4 * LPS, "Item holds the whole pool");
+}
+
+#[tokio::test]
+async fn cannot_fund_an_item_past_the_funder_limit() {
+	let mut ctx = setup().await;
+	let [owner, adder, late_funder] = create_users(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), None, None).await;
+	let item = add_item(&mut ctx, &list, &adder, "An item", LPS, None).await.unwrap();
+
+	let mut funders = Vec::new();
+	for _ in 0..todos::MAX_FUNDERS {
+		let funder = create_user(&mut ctx).await;
+		send(&mut ctx, &[fund_ix(&list, &item, &funder.pubkey(), 1)], &[&funder]).await.unwrap();
+		funders.push(funder.pubkey());
+	}
+
+	let result = send(&mut ctx, &[fund_ix(&list, &item, &late_funder.pubkey(), 1)], &[&late_funder]).await;
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::TooManyFunders));
+
+	let remaining = contribution_accounts(&item, &funders);
+	send(&mut ctx, &[cancel_ix(&list, &item, &adder.pubkey(), &owner.pubkey(), remaining)], &[&owner])
+		.await
+		.unwrap();
+	assert_eq!(balance(&mut ctx, &item).await, 0, "Item is closed");
+}
+
+#[tokio::test]
+async fn cannot_cancel_a_funded_item_without_every_contribution() {
+	let mut ctx = setup().await;
+	let [owner, adder, funder, other_funder] = create_users(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), None, None).await;
+	let item = add_item(&mut ctx, &list, &adder, "An item", LPS, None).await.unwrap();
+	send(&mut ctx, &[fund_ix(&list, &item, &funder.pubkey(), LPS)], &[&funder]).await.unwrap();
+	send(&mut ctx, &[fund_ix(&list, &item, &other_funder.pubkey(), LPS)], &[&other_funder]).await.unwrap();
+
+	let remaining = contribution_accounts(&item, &[funder.pubkey()]);
+	let result = send(&mut ctx, &[cancel_ix(&list, &item, &adder.pubkey(), &owner.pubkey(), remaining)], &[&owner]).await;
+
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::MissingContributions));
+	assert_eq!(balance(&mut ctx, &item).await, 3 * LPS, "Item balance is unchanged");
+}
+
+#[tokio::test]
+async fn can_cancel_a_funded_item_every_funder_is_refunded() {
+	let mut ctx = setup().await;
+	let [owner, adder, funder] = create_users(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), None, None).await;
+	let item = add_item(&mut ctx, &list, &adder, "An item", LPS, None).await.unwrap();
+	send(&mut ctx, &[fund_ix(&list, &item, &funder.pubkey(), 2 * LPS)], &[&funder]).await.unwrap();
+
+	let contribution = contribution_address(&item, &funder.pubkey());
+	let receipt_rent = balance(&mut ctx, &contribution).await;
+	let adder_balance = balance(&mut ctx, &adder.pubkey()).await;
+	let funder_balance = balance(&mut ctx, &funder.pubkey()).await;
+
+	let remaining = contribution_accounts(&item, &[funder.pubkey()]);
+	send(&mut ctx, &[cancel_ix(&list, &item, &adder.pubkey(), &owner.pubkey(), remaining)], &[&owner])
+		.await
+		.unwrap();
+
+	assert_eq!(balance(&mut ctx, &adder.pubkey()).await, adder_balance + LPS, "Creator gets their bounty back");
+	assert_eq!(
+		balance(&mut ctx, &funder.pubkey()).await,
+		funder_balance + 2 * LPS + receipt_rent,
+		"Funder gets their contribution back"
+	);
+	assert_eq!(balance(&mut ctx, &contribution).await, 0, "Contribution receipt is closed");
+}
+
+#[tokio::test]
+async fn can_finish_a_funded_item_the_pool_is_paid_to_the_list_owner() {
+	let mut ctx = setup().await;
+	let [owner, adder, funder] = create_users(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), None, None).await;
+	let item = add_item(&mut ctx, &list, &adder, "An item", LPS, None).await.unwrap();
+	send(&mut ctx, &[fund_ix(&list, &item, &funder.pubkey(), 2 * LPS)], &[&funder]).await.unwrap();
+
+	send(&mut ctx, &[finish_ix(&list, &owner.pubkey(), &item, &adder.pubkey(), vec![])], &[&adder])
+		.await
+		.unwrap();
+	let owner_balance = balance(&mut ctx, &owner.pubkey()).await;
+
+	let remaining = contribution_accounts(&item, &[funder.pubkey()]);
+	send(&mut ctx, &[finish_ix(&list, &owner.pubkey(), &item, &owner.pubkey(), remaining)], &[&owner])
+		.await
+		.unwrap();
+
+	assert_eq!(balance(&mut ctx, &owner.pubkey()).await, owner_balance + 3 * LPS, "Pool transferred to owner");
+}
+// <== }
+
+// { == Close lists ==>
+#[tokio::test]
+async fn can_close_an_empty_list_rent_returns_to_the_owner() {
+	let mut ctx = setup().await;
+	let owner = create_user(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), None, None).await;
+	let list_rent = balance(&mut ctx, &list.address).await;
+	let owner_balance = balance(&mut ctx, &owner.pubkey()).await;
+
+	send(&mut ctx, &[close_list_ix(&list, &owner.pubkey(), &[], &[])], &[&owner]).await.unwrap();
+
+	assert_eq!(balance(&mut ctx, &list.address).await, 0, "List is closed");
+	assert_eq!(balance(&mut ctx, &owner.pubkey()).await, owner_balance + list_rent, "Rent returns to owner");
+
+	let new_list = create_list(&mut ctx, &owner, "list", Some(16), None, None).await;
+	assert_eq!(new_list.address, list.address, "List name can be reused");
+}
+
+#[tokio::test]
+async fn cannot_close_a_list_other_user() {
+	let mut ctx = setup().await;
+	let [owner, other_user] = create_users(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), None, None).await;
+
+	let result = send(&mut ctx, &[close_list_ix(&list, &other_user.pubkey(), &[], &[])], &[&other_user]).await;
+
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::WrongListOwner));
+}
+
+#[tokio::test]
+async fn cannot_close_a_list_with_items_left() {
+	let mut ctx = setup().await;
+	let [owner, adder] = create_users(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), None, None).await;
+	let item = add_item(&mut ctx, &list, &adder, "An item", LPS, None).await.unwrap();
+	add_item(&mut ctx, &list, &adder, "Another item", LPS, None).await.unwrap();
+
+	let result = send(&mut ctx, &[close_list_ix(&list, &owner.pubkey(), &[adder.pubkey()], &[(item, adder.pubkey())])], &[&owner]).await;
+
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::ListNotEmpty));
+	assert_eq!(balance(&mut ctx, &item).await, LPS, "Item balance is unchanged");
+}
+
+#[tokio::test]
+async fn can_close_a_list_with_items_bounties_return_to_the_item_creators() {
+	let mut ctx = setup().await;
+	let [owner, adder, other_adder] = create_users(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), None, None).await;
+	let item = add_item(&mut ctx, &list, &adder, "An item", LPS, None).await.unwrap();
+	let other_item = add_item(&mut ctx, &list, &other_adder, "Another item", 2 * LPS, None).await.unwrap();
+
+	let page = first_page(&list);
+	let page_rent = balance(&mut ctx, &page).await;
+	let adder_balance = balance(&mut ctx, &adder.pubkey()).await;
+	let other_adder_balance = balance(&mut ctx, &other_adder.pubkey()).await;
+
+	let items = [(item, adder.pubkey()), (other_item, other_adder.pubkey())];
+	send(&mut ctx, &[close_list_ix(&list, &owner.pubkey(), &[adder.pubkey()], &items)], &[&owner])
+		.await
+		.unwrap();
+
+	assert_eq!(balance(&mut ctx, &list.address).await, 0, "List is closed");
+	assert_eq!(balance(&mut ctx, &page).await, 0, "Page is closed");
+	assert_eq!(
+		balance(&mut ctx, &adder.pubkey()).await,
+		adder_balance + LPS + page_rent,
+		"Bounty and page rent return to adder"
+	);
+	assert_eq!(
+		balance(&mut ctx, &other_adder.pubkey()).await,
+		other_adder_balance + 2 * LPS,
+		"Bounty returns to other adder"
+	);
+}
+// <== }
+
+// { == Resize lists ==>
+#[tokio::test]
+async fn can_grow_a_full_list() {
+	let mut ctx = setup().await;
+	let owner = create_user(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(1), None, None).await;
+	add_item(&mut ctx, &list, &owner, "Filler item", LPS, None).await.unwrap();
+
+	send(&mut ctx, &[resize_list_ix(&list, Some(2))], &[&owner]).await.unwrap();
+	add_item(&mut ctx, &list, &owner, "Another item", LPS, None).await.unwrap();
+
+	let (data, lines) = fetch_list(&mut ctx, &list.address).await;
+	assert_eq!(data.capacity, 2, "Capacity is updated");
+	assert_eq!(lines.len(), 2, "Item is added to the grown list");
+}
+
+#[tokio::test]
+async fn can_lift_the_cap_of_a_list() {
+	let mut ctx = setup().await;
+	let owner = create_user(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(1), None, None).await;
+	add_item(&mut ctx, &list, &owner, "Filler item", LPS, None).await.unwrap();
+
+	send(&mut ctx, &[resize_list_ix(&list, None)], &[&owner]).await.unwrap();
+	add_item(&mut ctx, &list, &owner, "Another item", LPS, None).await.unwrap();
+
+	let (data, lines) = fetch_list(&mut ctx, &list.address).await;
+	assert_eq!(data.capacity, 0, "Cap is lifted");
+	assert_eq!(lines.len(), 2, "Item is added past the old cap");
+}
+
+#[tokio::test]
+async fn cannot_shrink_a_list_below_its_item_count() {
+	let mut ctx = setup().await;
+	let owner = create_user(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(4), None, None).await;
+	add_item(&mut ctx, &list, &owner, "Filler item 1", LPS, None).await.unwrap();
+	add_item(&mut ctx, &list, &owner, "Filler item 2", LPS, None).await.unwrap();
+
+	let result = send(&mut ctx, &[resize_list_ix(&list, Some(1))], &[&owner]).await;
+
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::CapacityTooSmall));
+}
+// <== }
+
+// { == Pages ==>
+#[tokio::test]
+async fn can_add_items_past_a_full_page_the_next_page_is_opened() {
+	let mut ctx = setup().await;
+	let [owner, adder] = create_users(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list", None, None, None).await;
+	for i in 0..todos::PAGE_SIZE {
+		add_item(&mut ctx, &list, &owner, &format!("Item {}", i), LPS / 100, None).await.unwrap();
+	}
+
+	let item = add_item(&mut ctx, &list, &adder, "Item on the next page", LPS, None).await.unwrap();
+
+	let (data, lines) = fetch_list(&mut ctx, &list.address).await;
+	assert_eq!(data.page_count, 2, "Next page is opened");
+	assert_eq!(lines.len(), todos::PAGE_SIZE + 1, "Items on all pages are listed");
+	let page: ListPage = fetch_zero_copy(&mut ctx, &page_address(&list.address, 1)).await;
+	assert_eq!(page.payer, adder.pubkey(), "Adder opening the page pays for it");
+	assert_eq!(page.items(), &[item], "Item is on the next page");
+
+	let mut ix = cancel_ix(&list, &item, &adder.pubkey(), &adder.pubkey(), vec![]);
+	ix.accounts[2].pubkey = page_address(&list.address, 1);
+	send(&mut ctx, &[ix], &[&adder]).await.unwrap();
+
+	let (data, _) = fetch_list(&mut ctx, &list.address).await;
+	assert_eq!(data.len as usize, todos::PAGE_SIZE, "Item is removed from its page");
+}
+
+#[tokio::test]
+async fn cannot_add_an_item_to_a_page_past_the_next_page() {
+	let mut ctx = setup().await;
+	let owner = create_user(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list", None, None, None).await;
+
+	let data = todos::instruction::Add {
+		_list_name: list.name.clone(),
+		item_name: "An item".to_string(),
+		bounty: LPS,
+		deadline: None,
+		uri: None,
+		content_hash: [0; 32],
+		page_no: 1,
+	};
+	let result = send_add(&mut ctx, &list, &owner, data).await.map(|_| ());
+
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::WrongPage));
+}
+
+#[tokio::test]
+async fn cannot_use_the_page_of_another_list() {
+	let mut ctx = setup().await;
+	let owner = create_user(&mut ctx).await;
+	let list1 = create_list(&mut ctx, &owner, "list1", Some(16), None, None).await;
+	let list2 = create_list(&mut ctx, &owner, "list2", Some(16), None, None).await;
+	let item = add_item(&mut ctx, &list1, &owner, "An item", LPS, None).await.unwrap();
+	add_item(&mut ctx, &list2, &owner, "Another item", LPS, None).await.unwrap();
+
+	let mut ix = cancel_ix(&list2, &item, &owner.pubkey(), &owner.pubkey(), vec![]);
+	ix.accounts[2].pubkey = first_page(&list1);
+	let result = send(&mut ctx, &[ix], &[&owner]).await;
+
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::WrongPage));
+}
+
+#[tokio::test]
+async fn cannot_close_a_list_without_its_pages() {
+	let mut ctx = setup().await;
+	let owner = create_user(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), None, None).await;
+	let item = add_item(&mut ctx, &list, &owner, "An item", LPS, None).await.unwrap();
+	send(&mut ctx, &[cancel_ix(&list, &item, &owner.pubkey(), &owner.pubkey(), vec![])], &[&owner])
+		.await
+		.unwrap();
+
+	let result = send(&mut ctx, &[close_list_ix(&list, &owner.pubkey(), &[], &[])], &[&owner]).await;
+
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::MissingPages));
+}
+
+#[tokio::test]
+async fn can_close_emptied_pages_then_the_list_without_them() {
+	let mut ctx = setup().await;
+	let [owner, adder] = create_users(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list", None, None, None).await;
+	let mut items = Vec::with_capacity(todos::PAGE_SIZE);
+	for i in 0..todos::PAGE_SIZE {
+		items.push(add_item(&mut ctx, &list, &owner, &format!("Item {}", i), LPS / 100, None).await.unwrap());
+	}
+	let item = add_item(&mut ctx, &list, &adder, "Item on the next page", LPS, None).await.unwrap();
+
+	let result = send(&mut ctx, &[close_page_ix(&list, 1, &adder.pubkey())], &[]).await;
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::PageNotEmpty));
+
+	let mut ix = cancel_ix(&list, &item, &adder.pubkey(), &adder.pubkey(), vec![]);
+	ix.accounts[2].pubkey = page_address(&list.address, 1);
+	send(&mut ctx, &[ix], &[&adder]).await.unwrap();
+
+	let result = send(&mut ctx, &[close_page_ix(&list, 0, &owner.pubkey())], &[]).await;
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::NotLastPage));
+
+	let page_rent = balance(&mut ctx, &page_address(&list.address, 1)).await;
+	let adder_balance = balance(&mut ctx, &adder.pubkey()).await;
+	send(&mut ctx, &[close_page_ix(&list, 1, &adder.pubkey())], &[]).await.unwrap();
+	assert_eq!(balance(&mut ctx, &adder.pubkey()).await, adder_balance + page_rent, "Page rent returns to whoever opened it");
+	let (data, _) = fetch_list(&mut ctx, &list.address).await;
+	assert_eq!(data.page_count, 1, "Page is closed");
+
+	// Removing the last item of a page moves nothing
+	for item in items.iter().rev() {
+		send(&mut ctx, &[cancel_ix(&list, item, &owner.pubkey(), &owner.pubkey(), vec![])], &[&owner])
+			.await
+			.unwrap();
+	}
+	next_slot(&mut ctx).await;
+	send(&mut ctx, &[close_page_ix(&list, 0, &owner.pubkey())], &[]).await.unwrap();
+	send(&mut ctx, &[close_list_ix(&list, &owner.pubkey(), &[], &[])], &[&owner])
+		.await
+		.unwrap();
+
+	assert_eq!(balance(&mut ctx, &list.address).await, 0, "List is closed without pages");
+}
+// <== }
+
+// { == Legacy lists ==>
+#[tokio::test]
+async fn can_reclaim_a_legacy_item_bounty_returns_to_the_creator() {
+	let mut ctx = setup().await;
+	let [owner, adder] = create_users(&mut ctx).await;
+	let item = set_legacy_item(&mut ctx, &adder.pubkey(), "An item", LPS);
+	let other_item = set_legacy_item(&mut ctx, &owner.pubkey(), "Another item", LPS);
+	let list = set_legacy_list(&mut ctx, &owner.pubkey(), "A legacy list", &[item, other_item]);
+	let adder_balance = balance(&mut ctx, &adder.pubkey()).await;
+
+	// Only the fee payer signs, anyone may reclaim
+	send(&mut ctx, &[reclaim_legacy_item_ix(&list, &item, &adder.pubkey())], &[]).await.unwrap();
+
+	assert_eq!(balance(&mut ctx, &item).await, 0, "Item is closed");
+	assert_eq!(balance(&mut ctx, &adder.pubkey()).await, adder_balance + LPS, "Bounty returns to the creator");
+	assert_eq!(fetch_legacy_lines(&mut ctx, &list.address).await, vec![other_item], "Item is taken off the list");
+
+	let result = send(&mut ctx, &[reclaim_legacy_item_ix(&list, &item, &owner.pubkey())], &[]).await;
+	assert_eq!(error_code(result), anchor_error(anchor_lang::error::ErrorCode::ConstraintOwner));
+}
+
+#[tokio::test]
+async fn cannot_reclaim_a_legacy_item_wrong_creator_or_list() {
+	let mut ctx = setup().await;
+	let [owner, adder, other_user] = create_users(&mut ctx).await;
+	let item = set_legacy_item(&mut ctx, &adder.pubkey(), "An item", LPS);
+	let list = set_legacy_list(&mut ctx, &owner.pubkey(), "A legacy list", &[item]);
+	let other_list = set_legacy_list(&mut ctx, &owner.pubkey(), "Another legacy list", &[]);
+
+	let result = send(&mut ctx, &[reclaim_legacy_item_ix(&list, &item, &other_user.pubkey())], &[]).await;
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::WrongItemCreator));
+
+	let result = send(&mut ctx, &[reclaim_legacy_item_ix(&other_list, &item, &adder.pubkey())], &[]).await;
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::ItemNotFound));
+	assert_eq!(balance(&mut ctx, &item).await, LPS, "Item balance is unchanged");
+}
+
+#[tokio::test]
+async fn cannot_reclaim_current_lists_and_items() {
+	let mut ctx = setup().await;
+	let [owner, adder] = create_users(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), None, None).await;
+	let item = add_item(&mut ctx, &list, &adder, "An item", LPS, None).await.unwrap();
+	let legacy_item = set_legacy_item(&mut ctx, &adder.pubkey(), "A legacy item", LPS);
+	let legacy_list = set_legacy_list(&mut ctx, &owner.pubkey(), "A legacy list", &[item, legacy_item]);
+
+	let result = send(&mut ctx, &[reclaim_legacy_item_ix(&legacy_list, &item, &adder.pubkey())], &[]).await;
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::NotLegacyAccount));
+
+	let result = send(&mut ctx, &[reclaim_legacy_item_ix(&list, &legacy_item, &adder.pubkey())], &[]).await;
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::NotLegacyAccount));
+
+	let result = send(&mut ctx, &[close_legacy_list_ix(&list, &owner.pubkey())], &[&owner]).await;
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::NotLegacyAccount));
+	assert_eq!(balance(&mut ctx, &item).await, LPS, "Item balance is unchanged");
+}
+
+#[tokio::test]
+async fn can_close_a_legacy_list_once_its_items_are_reclaimed() {
+	let mut ctx = setup().await;
+	let [owner, adder, other_user] = create_users(&mut ctx).await;
+	let item = set_legacy_item(&mut ctx, &adder.pubkey(), "An item", LPS);
+	let list = set_legacy_list(&mut ctx, &owner.pubkey(), "A legacy list", &[item]);
+
+	let result = send(&mut ctx, &[close_legacy_list_ix(&list, &other_user.pubkey())], &[&other_user]).await;
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::WrongListOwner));
+
+	let result = send(&mut ctx, &[close_legacy_list_ix(&list, &owner.pubkey())], &[&owner]).await;
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::ListNotEmpty));
+
+	let list_rent = balance(&mut ctx, &list.address).await;
+	let owner_balance = balance(&mut ctx, &owner.pubkey()).await;
+	send(
+		&mut ctx,
+		&[reclaim_legacy_item_ix(&list, &item, &adder.pubkey()), close_legacy_list_ix(&list, &owner.pubkey())],
+		&[&owner],
+	)
+	.await
+	.unwrap();
+
+	assert_eq!(balance(&mut ctx, &list.address).await, 0, "List is closed");
+	assert_eq!(balance(&mut ctx, &owner.pubkey()).await, owner_balance + list_rent, "Rent returns to owner");
+
+	let (ix, address) = new_list_ix(&owner.pubkey(), "A legacy list", Some(16), None, None, SeedScheme::Truncated);
+	send(&mut ctx, &[ix], &[&owner]).await.unwrap();
+	assert_eq!(address, list.address, "List is created again under its legacy address");
+}
+// <== }
+
+// { == Transfer ownership ==>
+#[tokio::test]
+async fn cannot_accept_a_list_other_user() {
+	let mut ctx = setup().await;
+	let [owner, new_owner, other_user] = create_users(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), None, None).await;
+	send(&mut ctx, &[propose_owner_ix(&list, &owner.pubkey(), Some(new_owner.pubkey()))], &[&owner])
+		.await
+		.unwrap();
+
+	let result = send(&mut ctx, &[accept_owner_ix(&list, &other_user.pubkey())], &[&other_user]).await;
+
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::NotPendingOwner));
+}
+
+#[tokio::test]
+async fn cannot_propose_an_owner_other_user() {
+	let mut ctx = setup().await;
+	let [owner, other_user] = create_users(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), None, None).await;
+
+	let result = send(&mut ctx, &[propose_owner_ix(&list, &other_user.pubkey(), Some(other_user.pubkey()))], &[&other_user]).await;
+
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::WrongListOwner));
+}
+
+#[tokio::test]
+async fn can_transfer_a_list_new_owner_takes_over_items_at_the_same_address() {
+	let mut ctx = setup().await;
+	let [owner, new_owner, adder] = create_users(&mut ctx).await;
+	let mut list = create_list(&mut ctx, &owner, "list", Some(16), None, None).await;
+	let item = add_item(&mut ctx, &list, &adder, "An item", LPS, None).await.unwrap();
+
+	send(&mut ctx, &[propose_owner_ix(&list, &owner.pubkey(), Some(new_owner.pubkey()))], &[&owner])
+		.await
+		.unwrap();
+	send(&mut ctx, &[accept_owner_ix(&list, &new_owner.pubkey())], &[&new_owner]).await.unwrap();
+
+	let (data, _) = fetch_list(&mut ctx, &list.address).await;
+	assert_eq!(data.list_owner, new_owner.pubkey(), "Owner is updated");
+	assert_eq!(data.pending_owner, Pubkey::default(), "Nomination is cleared");
+	list.owner = new_owner.pubkey();
+
+	let result = send(&mut ctx, &[finish_ix(&list, &owner.pubkey(), &item, &owner.pubkey(), vec![])], &[&owner]).await;
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::WrongListOwner));
+
+	let new_owner_balance = balance(&mut ctx, &new_owner.pubkey()).await;
+	send(&mut ctx, &[finish_ix(&list, &new_owner.pubkey(), &item, &new_owner.pubkey(), vec![])], &[&new_owner])
+		.await
+		.unwrap();
+	send(&mut ctx, &[finish_ix(&list, &new_owner.pubkey(), &item, &adder.pubkey(), vec![])], &[&adder])
+		.await
+		.unwrap();
+	assert_eq!(balance(&mut ctx, &new_owner.pubkey()).await, new_owner_balance + LPS, "Bounty transferred to new owner");
+
+	add_item(&mut ctx, &list, &adder, "Another item", LPS, None).await.unwrap();
+}
+// <== }
+
+// { == Moderators ==>
+#[tokio::test]
+async fn can_cancel_item_moderator_with_cancel_role() {
+	let mut ctx = setup().await;
+	let [owner, adder, moderator] = create_users(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), None, None).await;
+	let item = add_item(&mut ctx, &list, &adder, "An item", LPS, None).await.unwrap();
+	send(&mut ctx, &[set_moderator_ix(&list, &moderator.pubkey(), true, false)], &[&owner]).await.unwrap();
+
+	let adder_balance = balance(&mut ctx, &adder.pubkey()).await;
+	send(&mut ctx, &[cancel_ix(&list, &item, &adder.pubkey(), &moderator.pubkey(), vec![])], &[&moderator])
+		.await
+		.unwrap();
+
+	assert_eq!(balance(&mut ctx, &adder.pubkey()).await, adder_balance + LPS, "Cancel returns bounty to adder");
+}
+
+#[tokio::test]
+async fn cannot_cancel_item_moderator_without_cancel_role() {
+	let mut ctx = setup().await;
+	let [owner, adder, moderator] = create_users(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), None, None).await;
+	let item = add_item(&mut ctx, &list, &adder, "An item", LPS, None).await.unwrap();
+	send(&mut ctx, &[set_moderator_ix(&list, &moderator.pubkey(), false, true)], &[&owner]).await.unwrap();
+
+	let result = send(&mut ctx, &[cancel_ix(&list, &item, &adder.pubkey(), &moderator.pubkey(), vec![])], &[&moderator]).await;
+
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::ModeratorCannotCancel));
+}
+
+#[tokio::test]
+async fn can_finish_item_moderator_marks_the_owner_side() {
+	let mut ctx = setup().await;
+	let [owner, adder, moderator] = create_users(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), None, None).await;
+	let item = add_item(&mut ctx, &list, &adder, "An item", LPS, None).await.unwrap();
+	send(&mut ctx, &[set_moderator_ix(&list, &moderator.pubkey(), false, true)], &[&owner]).await.unwrap();
+
+	let owner_balance = balance(&mut ctx, &owner.pubkey()).await;
+	send(&mut ctx, &[finish_ix(&list, &owner.pubkey(), &item, &moderator.pubkey(), vec![])], &[&moderator])
+		.await
+		.unwrap();
+
+	let data: ListItem = fetch(&mut ctx, &item).await;
+	assert!(data.list_owner_finished, "Owner finish flag gets set by moderator");
+
+	send(&mut ctx, &[finish_ix(&list, &owner.pubkey(), &item, &adder.pubkey(), vec![])], &[&adder])
+		.await
+		.unwrap();
+	assert_eq!(balance(&mut ctx, &owner.pubkey()).await, owner_balance + LPS, "Bounty transferred to owner");
+}
+
+#[tokio::test]
+async fn cannot_finish_item_removed_moderator() {
+	let mut ctx = setup().await;
+	let [owner, adder, moderator] = create_users(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), None, None).await;
+	let item = add_item(&mut ctx, &list, &adder, "An item", LPS, None).await.unwrap();
+	send(&mut ctx, &[set_moderator_ix(&list, &moderator.pubkey(), true, true)], &[&owner]).await.unwrap();
+	send(&mut ctx, &[remove_moderator_ix(&list, &moderator.pubkey())], &[&owner]).await.unwrap();
+
+	let result = send(&mut ctx, &[finish_ix(&list, &owner.pubkey(), &item, &moderator.pubkey(), vec![])], &[&moderator]).await;
+
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::FinishPermissions));
+}
+
+#[tokio::test]
+async fn cannot_close_a_list_moderators_left() {
+	let mut ctx = setup().await;
+	let [owner, moderator] = create_users(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), None, None).await;
+	send(&mut ctx, &[set_moderator_ix(&list, &moderator.pubkey(), true, false)], &[&owner]).await.unwrap();
+	send(&mut ctx, &[set_moderator_ix(&list, &moderator.pubkey(), true, true)], &[&owner]).await.unwrap();
+	let (data, _) = fetch_list(&mut ctx, &list.address).await;
+	assert_eq!(data.moderator_count, 1, "Replacing roles keeps the count");
+
+	let result = send(&mut ctx, &[close_list_ix(&list, &owner.pubkey(), &[], &[])], &[&owner]).await;
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::ModeratorsLeft));
+
+	send(&mut ctx, &[remove_moderator_ix(&list, &moderator.pubkey())], &[&owner]).await.unwrap();
+	next_slot(&mut ctx).await;
+	send(&mut ctx, &[close_list_ix(&list, &owner.pubkey(), &[], &[])], &[&owner]).await.unwrap();
+	assert_eq!(balance(&mut ctx, &list.address).await, 0, "List is closed");
+
+	let new_list = create_list(&mut ctx, &owner, "list", Some(16), None, None).await;
+	assert_eq!(balance(&mut ctx, &moderator_address(&new_list.address, &moderator.pubkey())).await, 0, "Moderator does not carry over");
+}
+// <== }
+
+// { == Edit items ==>
+#[tokio::test]
+async fn can_rename_an_item_bounty_is_kept() {
+	let mut ctx = setup().await;
+	let [owner, adder] = create_users(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), None, None).await;
+	let item = add_item(&mut ctx, &list, &adder, "A tpyo", LPS, None).await.unwrap();
+
+	let uri = Some("https://example.com/spec.md".to_string());
+	send(&mut ctx, &[edit_item_ix(&list, &item, &adder.pubkey(), "A typo fixed with a much longer name", uri.clone())], &[&adder])
+		.await
+		.unwrap();
+
+	let data: ListItem = fetch(&mut ctx, &item).await;
+	assert_eq!(data.name, "A typo fixed with a much longer name", "Name is updated");
+	assert_eq!(data.uri, uri, "Uri is updated");
+	assert_eq!(data.content_hash, [1; 32], "Content hash is updated");
+	assert_eq!(balance(&mut ctx, &item).await, LPS, "Item keeps its bounty");
+}
+
+#[tokio::test]
+async fn cannot_edit_an_item_other_user() {
+	let mut ctx = setup().await;
+	let [owner, adder] = create_users(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), None, None).await;
+	let item = add_item(&mut ctx, &list, &adder, "An item", LPS, None).await.unwrap();
+
+	let result = send(&mut ctx, &[edit_item_ix(&list, &item, &owner.pubkey(), "Renamed", None)], &[&owner]).await;
+
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::EditPermissions));
+}
+
+#[tokio::test]
+async fn cannot_edit_an_item_once_marked_finished() {
+	let mut ctx = setup().await;
+	let [owner, adder] = create_users(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), None, None).await;
+	let item = add_item(&mut ctx, &list, &adder, "An item", LPS, None).await.unwrap();
+	send(&mut ctx, &[finish_ix(&list, &owner.pubkey(), &item, &owner.pubkey(), vec![])], &[&owner])
+		.await
+		.unwrap();
+
+	let result = send(&mut ctx, &[edit_item_ix(&list, &item, &adder.pubkey(), "Something else", None)], &[&adder]).await;
+
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::ItemLocked));
+}
+
+#[tokio::test]
+async fn cannot_edit_an_item_once_funded() {
+	let mut ctx = setup().await;
+	let [owner, adder, funder] = create_users(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), None, None).await;
+	let item = add_item(&mut ctx, &list, &adder, "An item", LPS, None).await.unwrap();
+	send(&mut ctx, &[fund_ix(&list, &item, &funder.pubkey(), LPS)], &[&funder]).await.unwrap();
+
+	let result = send(&mut ctx, &[edit_item_ix(&list, &item, &adder.pubkey(), "Something else", None)], &[&adder]).await;
+
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::ItemHasFunders));
+	assert_eq!(fetch::<ListItem>(&mut ctx, &item).await.name, "An item", "Name is unchanged");
+}
+// <== }
+
+// { == Item descriptions ==>
+#[tokio::test]
+async fn can_add_an_item_with_a_description_uri_and_content_hash() {
+	let mut ctx = setup().await;
+	let [owner, adder] = create_users(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), None, None).await;
+	let uri = "ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi".to_string();
+
+	let data = todos::instruction::Add {
+		_list_name: list.name.clone(),
+		item_name: "An item".to_string(),
+		bounty: LPS,
+		deadline: None,
+		uri: Some(uri.clone()),
+		content_hash: [7; 32],
+		page_no: 0,
+	};
+	let item = send_add(&mut ctx, &list, &adder, data).await.unwrap();
+
+	let data: ListItem = fetch(&mut ctx, &item).await;
+	assert_eq!(data.uri, Some(uri), "Uri is set");
+	assert_eq!(data.content_hash, [7; 32], "Content hash is set");
+}
+
+#[tokio::test]
+async fn cannot_add_an_item_with_a_uri_that_is_too_long() {
+	let mut ctx = setup().await;
+	let [owner, adder] = create_users(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), None, None).await;
+
+	let data = todos::instruction::Add {
+		_list_name: list.name.clone(),
+		item_name: "An item".to_string(),
+		bounty: LPS,
+		deadline: None,
+		uri: Some(format!("https://{}", "a".repeat(200))),
+		content_hash: [0; 32],
+		page_no: 0,
+	};
+	let result = send_add(&mut ctx, &list, &adder, data).await.map(|_| ());
+
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::InvalidUri));
+}
+
+#[tokio::test]
+async fn can_edit_the_description_of_an_item() {
+	let mut ctx = setup().await;
+	let [owner, adder] = create_users(&mut ctx).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), None, None).await;
+	let item = add_item(&mut ctx, &list, &adder, "An item", LPS, None).await.unwrap();
+
+	let uri = Some("https://example.com/spec.md".to_string());
+	send(&mut ctx, &[edit_item_ix(&list, &item, &adder.pubkey(), "An item", uri.clone())], &[&adder])
+		.await
+		.unwrap();
+
+	let data: ListItem = fetch(&mut ctx, &item).await;
+	assert_eq!(data.uri, uri, "Uri is updated");
+	assert_eq!(data.content_hash, [1; 32], "Content hash is updated");
+}
+// <== }
+
+// { == Protocol fee ==>
+#[tokio::test]
+async fn can_initialize_the_config() {
+	let mut ctx = setup().await;
+	let admin = create_user(&mut ctx).await;
+
+	init_config(&mut ctx, &admin, 250, treasury()).await;
+
+	let config: Config = fetch(&mut ctx, &config_address()).await;
+	assert_eq!(config.admin, admin.pubkey(), "Initializer is the admin");
+	assert_eq!(config.fee_bps, 250, "Fee is set");
+	assert_eq!(config.treasury, treasury(), "Treasury is set");
+}
+
+#[tokio::test]
+async fn cannot_initialize_the_config_not_the_upgrade_authority() {
+	let mut ctx = setup().await;
+	let [authority, user] = create_users(&mut ctx).await;
+	set_upgrade_authority(&mut ctx, &authority.pubkey());
+
+	let result = send(&mut ctx, &[init_config_ix(&user.pubkey(), 10_000, user.pubkey())], &[&user]).await;
+
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::NotUpgradeAuthority));
+}
+
+#[tokio::test]
+async fn cannot_update_the_config_other_user() {
+	let mut ctx = setup().await;
+	let [admin, user] = create_users(&mut ctx).await;
+	init_config(&mut ctx, &admin, 0, treasury()).await;
+
+	let result = send(&mut ctx, &[update_config_ix(&user.pubkey(), 500, user.pubkey())], &[&user]).await;
+
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::NotAdmin));
+}
+
+#[tokio::test]
+async fn cannot_set_a_fee_above_1000_bps() {
+	let mut ctx = setup().await;
+	let admin = create_user(&mut ctx).await;
+
+	set_upgrade_authority(&mut ctx, &admin.pubkey());
+
+	let result = send(&mut ctx, &[init_config_ix(&admin.pubkey(), 1_001, treasury())], &[&admin]).await;
+
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::InvalidFee));
+}
+
+#[tokio::test]
+async fn cannot_finish_an_item_with_the_wrong_treasury() {
+	let mut ctx = setup().await;
+	let [admin, owner, adder] = create_users(&mut ctx).await;
+	init_config(&mut ctx, &admin, 500, admin.pubkey()).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), None, None).await;
+	let item = add_item(&mut ctx, &list, &adder, "An item", LPS, None).await.unwrap();
+	send(&mut ctx, &[finish_ix(&list, &owner.pubkey(), &item, &owner.pubkey(), vec![])], &[&owner])
+		.await
+		.unwrap();
+
+	let result = send(&mut ctx, &[finish_ix(&list, &owner.pubkey(), &item, &adder.pubkey(), vec![])], &[&adder]).await;
+
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::WrongTreasury));
+}
+
+#[tokio::test]
+async fn can_finish_an_item_the_fee_goes_to_the_treasury() {
+	let mut ctx = setup().await;
+	let [admin, owner, adder] = create_users(&mut ctx).await;
+	init_config(&mut ctx, &admin, 500, treasury()).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), None, None).await;
+	let owner_initial = balance(&mut ctx, &owner.pubkey()).await;
+	let item = add_item(&mut ctx, &list, &adder, "An item", 2 * LPS, None).await.unwrap();
+
+	send(&mut ctx, &[finish_ix(&list, &owner.pubkey(), &item, &owner.pubkey(), vec![])], &[&owner])
+		.await
+		.unwrap();
+	send(&mut ctx, &[finish_ix(&list, &owner.pubkey(), &item, &adder.pubkey(), vec![])], &[&adder])
+		.await
+		.unwrap();
+
+	assert_eq!(balance(&mut ctx, &treasury()).await, LPS / 10, "Treasury receives 5% of the bounty");
+	assert_eq!(balance(&mut ctx, &owner.pubkey()).await, owner_initial + 2 * LPS - LPS / 10, "Owner receives the rest");
+}
+
+#[tokio::test]
+async fn can_finish_an_item_the_fee_is_the_one_it_was_added_with() {
+	let mut ctx = setup().await;
+	let [admin, owner, adder] = create_users(&mut ctx).await;
+	init_config(&mut ctx, &admin, 500, treasury()).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), None, None).await;
+	let item = add_item(&mut ctx, &list, &adder, "An item", 2 * LPS, None).await.unwrap();
+	assert_eq!(fetch::<ListItem>(&mut ctx, &item).await.fee_bps, 500, "Item records the fee");
+
+	send(&mut ctx, &[update_config_ix(&admin.pubkey(), 1_000, treasury())], &[&admin])
+		.await
+		.unwrap();
+	send(&mut ctx, &[finish_ix(&list, &owner.pubkey(), &item, &owner.pubkey(), vec![])], &[&owner])
+		.await
+		.unwrap();
+	send(&mut ctx, &[finish_ix(&list, &owner.pubkey(), &item, &adder.pubkey(), vec![])], &[&adder])
+		.await
+		.unwrap();
+
+	assert_eq!(balance(&mut ctx, &treasury()).await, LPS / 10, "Treasury receives the 5% the item was added with");
+}
+
+#[tokio::test]
+async fn can_resolve_a_dispute_the_fee_is_taken_from_the_owner_share() {
+	let mut ctx = setup().await;
+	let [admin, owner, adder, arbiter] = create_users(&mut ctx).await;
+	init_config(&mut ctx, &admin, 500, treasury()).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), Some(arbiter.pubkey()), None).await;
+	let item = add_item(&mut ctx, &list, &adder, "An item", 2 * LPS, None).await.unwrap();
+	send(&mut ctx, &[open_dispute_ix(&list, &item, &owner.pubkey())], &[&owner]).await.unwrap();
+
+	let owner_balance = balance(&mut ctx, &owner.pubkey()).await;
+	let adder_balance = balance(&mut ctx, &adder.pubkey()).await;
+
+	send(
+		&mut ctx,
+		&[resolve_dispute_ix(&list, &item, &adder.pubkey(), &arbiter.pubkey(), 5_000, vec![])],
+		&[&arbiter],
+	)
+	.await
+	.unwrap();
+
+	assert_eq!(balance(&mut ctx, &treasury()).await, LPS / 20, "Treasury receives 5% of the owner share");
+	assert_eq!(balance(&mut ctx, &owner.pubkey()).await, owner_balance + LPS - LPS / 20, "Owner receives the rest");
+	assert_eq!(balance(&mut ctx, &adder.pubkey()).await, adder_balance + LPS, "Creator's refund pays no fee");
+}
+// <== }
+
+// { == Pause ==>
+#[tokio::test]
+async fn cannot_pause_the_program_other_user() {
+	let mut ctx = setup().await;
+	let [admin, user] = create_users(&mut ctx).await;
+	init_config(&mut ctx, &admin, 0, treasury()).await;
+
+	let result = send(&mut ctx, &[set_pause_ix(&user.pubkey(), true, false, false, false)], &[&user]).await;
+
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::NotAdmin));
+}
+
+#[tokio::test]
+async fn cannot_add_or_fund_items_while_deposits_are_paused() {
+	let mut ctx = setup().await;
+	let [admin, owner, adder] = create_users(&mut ctx).await;
+	init_config(&mut ctx, &admin, 0, treasury()).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), None, None).await;
+	let item = add_item(&mut ctx, &list, &adder, "An item", LPS, None).await.unwrap();
+
+	send(&mut ctx, &[set_pause_ix(&admin.pubkey(), false, true, false, false)], &[&admin])
+		.await
+		.unwrap();
+
+	let result = add_item(&mut ctx, &list, &adder, "Another item", LPS, None).await;
+	assert_eq!(error_code(result.map(|_| ())), program_error(todos::ErrorCode::ProgramPaused));
+	let result = send(&mut ctx, &[fund_ix(&list, &item, &owner.pubkey(), LPS)], &[&owner]).await;
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::ProgramPaused));
+	send(&mut ctx, &[finish_ix(&list, &owner.pubkey(), &item, &owner.pubkey(), vec![])], &[&owner])
+		.await
+		.unwrap();
+}
+
+#[tokio::test]
+async fn cannot_pay_out_items_while_payouts_are_paused() {
+	let mut ctx = setup().await;
+	let [admin, owner, adder] = create_users(&mut ctx).await;
+	init_config(&mut ctx, &admin, 0, treasury()).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), None, None).await;
+	let item = add_item(&mut ctx, &list, &adder, "An item", LPS, None).await.unwrap();
+	send(&mut ctx, &[finish_ix(&list, &owner.pubkey(), &item, &owner.pubkey(), vec![])], &[&owner])
+		.await
+		.unwrap();
+
+	send(&mut ctx, &[set_pause_ix(&admin.pubkey(), false, false, true, false)], &[&admin])
+		.await
+		.unwrap();
+
+	let result = send(&mut ctx, &[finish_ix(&list, &owner.pubkey(), &item, &adder.pubkey(), vec![])], &[&adder]).await;
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::ProgramPaused));
+	let result = send(&mut ctx, &[cancel_ix(&list, &item, &adder.pubkey(), &owner.pubkey(), vec![])], &[&owner]).await;
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::ProgramPaused));
+}
+
+#[tokio::test]
+async fn can_cancel_items_while_paused_with_refunds_open() {
+	let mut ctx = setup().await;
+	let [admin, owner, adder] = create_users(&mut ctx).await;
+	init_config(&mut ctx, &admin, 0, treasury()).await;
+	let list = create_list(&mut ctx, &owner, "list", Some(16), None, None).await;
+	let item = add_item(&mut ctx, &list, &adder, "An item", LPS, None).await.unwrap();
+	let adder_balance = balance(&mut ctx, &adder.pubkey()).await;
+
+	send(&mut ctx, &[set_pause_ix(&admin.pubkey(), true, false, false, false)], &[&admin])
+		.await
+		.unwrap();
+
+	let result = send(&mut ctx, &[resize_list_ix(&list, None)], &[&owner]).await;
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::ProgramPaused));
+	let result = send(&mut ctx, &[cancel_ix(&list, &item, &adder.pubkey(), &adder.pubkey(), vec![])], &[&adder]).await;
+	assert_eq!(error_code(result), program_error(todos::ErrorCode::ProgramPaused));
+
+	send(&mut ctx, &[set_pause_ix(&admin.pubkey(), true, false, false, true)], &[&admin])
+		.await
+		.unwrap();
+	send(&mut ctx, &[cancel_ix(&list, &item, &adder.pubkey(), &adder.pubkey(), vec![])], &[&adder])
+		.await
+		.unwrap();
+
+	let (_, lines) = fetch_list(&mut ctx, &list.address).await;
+	assert!(lines.is_empty(), "Item removed from list");
+	assert_eq!(balance(&mut ctx, &adder.pubkey()).await, adder_balance + LPS, "Bounty refunded to the item creator");
+}
+// <== }
@@ -1,5 +1,6 @@
 import * as anchor from "@project-serum/anchor";
 import { PublicKey, Keypair } from "@solana/web3.js";
+import { createMint, createAccount, mintTo, getAccount, TOKEN_PROGRAM_ID } from "@solana/spl-token";
 import { Todos } from "../target/types/todos";
 import { expect } from "chai"
 import { BN } from "bn.js";
@@ -42,37 +43,142 @@ describe('todos', () => {
 		expect(actual, message).within(expected - slack, expected + slack)
 	}
 
-	const createList = async (owner: any, name: string, capacity = 16) => {
+	const HASHED = { hashed: {} };
+	const TRUNCATED = { truncated: {} };
+
+	// Hashed lists are derived from the SHA-256 of the full name, legacy lists from its first 32 bytes
+	const listSeed = (name: string, seedScheme) => {
+		return seedScheme === TRUNCATED ? Buffer.from(name).slice(0, 32) : Buffer.from(anchor.utils.sha256.hash(name), "hex");
+	}
+
+	const createList = async (owner: any, name: string, capacity = 16, arbiter: PublicKey = null, reviewWindow: number = null, seedScheme = HASHED) => {
 		const [listAccount, bump] = await PublicKey.findProgramAddress([
 			"todolist",
 			owner.publicKey.toBuffer(),
-			name.slice(0, 32)
+			listSeed(name, seedScheme)
 		], program.programId);
 
-		await program.methods.newList(name, capacity, bump)
-			.accounts({ list: listAccount, user: owner.publicKey })
+		await program.methods.newList(name, capacity, arbiter, reviewWindow === null ? null : new BN(reviewWindow), seedScheme)
+			.accounts({ list: listAccount, user: owner.publicKey, config: await configAddress() })
 			.signers(owner instanceof (anchor.Wallet as any) ? [] : [owner])
 			.rpc();
 
-		let list = await program.account.todoList.fetch(listAccount);
-		return { publicKey: listAccount, data: list };
+		let list = await fetchList(listAccount);
+		return { publicKey: listAccount, data: list, bump };
+	}
+
+	const PAGE_SIZE = 64;
+
+	// Settling an item takes a contribution and funder account per funder in one transaction
+	const MAX_FUNDERS = 8;
+
+	const pageAddress = async (list: PublicKey, pageNo: number) => {
+		const [page] = await PublicKey.findProgramAddress([
+			"page",
+			list.toBuffer(),
+			new BN(pageNo).toArrayLike(Buffer, "le", 4)
+		], program.programId);
+		return page;
+	}
+
+	const fetchPages = async (list: PublicKey, pageCount: number) => {
+		const addresses = await Promise.all([...Array(pageCount).keys()].map((pageNo) => pageAddress(list, pageNo)));
+		const pages = await program.account.listPage.fetchMultiple(addresses);
+		return pages.map((page: any, pageNo) => ({ publicKey: addresses[pageNo], lines: page.lines.slice(0, page.len) }));
+	}
+
+	// Lists are a zero-copy header, their open items are kept on pages
+	const fetchList = async (list: PublicKey) => {
+		const header = await program.account.listHeader.fetch(list);
+		const pages = await fetchPages(list, header.pageCount);
+
+		const optional = (key: PublicKey) => key.equals(PublicKey.default) ? null : key;
+		return {
+			...header,
+			name: Buffer.from(header.name.slice(0, header.nameLen)).toString(),
+			pendingOwner: optional(header.pendingOwner),
+			arbiter: optional(header.arbiter),
+			lines: pages.flatMap((page) => page.lines),
+		};
+	}
+
+	// Items are added to the last page until it is full
+	const nextPageNo = async (list: PublicKey) => {
+		const { pageCount } = await program.account.listHeader.fetch(list);
+		if (pageCount === 0) return 0;
+		const lastPage = await program.account.listPage.fetch(await pageAddress(list, pageCount - 1));
+		return lastPage.len < PAGE_SIZE ? pageCount - 1 : pageCount;
+	}
+
+	// Removing an item moves the last item of its page into the freed slot
+	const removalAccounts = async (list: PublicKey, item: PublicKey) => {
+		const { pageNo } = await program.account.listItem.fetch(item);
+		const page = await pageAddress(list, pageNo);
+		const { lines, len } = await program.account.listPage.fetch(page);
+		return { page, movedItem: lines[len - 1] };
+	}
+
+	const configAddress = async () => {
+		const [config] = await PublicKey.findProgramAddress(["config"], program.programId);
+		return config;
+	}
+
+	// Payouts take a fee once the config exists, until then any treasury is accepted
+	const feeAccounts = async (fallbackTreasury: PublicKey) => {
+		const config = await configAddress();
+		const configData = await program.account.config.fetchNullable(config);
+		return { config, treasury: configData ? configData.treasury : fallbackTreasury };
+	}
+
+	const sleep = (ms: number) => new Promise((resolve) => setTimeout(resolve, ms));
+
+	const now = () => Math.floor(Date.now() / 1000);
+
+	const captureEvents = async (names: string[], action: () => Promise<any>) => {
+		const events = [];
+		const listeners = names.map((name) => program.addEventListener(name, (event) => events.push({ name, event })));
+		await action();
+		// Give the log subscription time to deliver the events
+		await sleep(1000);
+		await Promise.all(listeners.map((listener) => program.removeEventListener(listener)));
+		return events;
+	}
+
+	const EMPTY_HASH = new Array(32).fill(0);
+
+	const itemAddress = async (list: PublicKey, index: number) => {
+		const [item] = await PublicKey.findProgramAddress([
+			"item",
+			list.toBuffer(),
+			new BN(index).toArrayLike(Buffer, "le", 8)
+		], program.programId);
+		return item;
+	}
+
+	// Items are derived from the number of items the list has seen so far
+	const nextItemAccount = async (list: PublicKey) => {
+		const listData = await fetchList(list);
+		return { publicKey: await itemAddress(list, listData.itemCount.toNumber()) };
 	}
 
-	const addItem = async ({ list, user, name, bounty }) => {
-		const itemAccount = Keypair.generate();
-		await program.methods.add(list.data.name, name, new BN(bounty))
+	const addItem = async ({ list, user, name, bounty, deadline = null, uri = null, contentHash = EMPTY_HASH }) => {
+		const itemAccount = await nextItemAccount(list.publicKey);
+		const pageNo = await nextPageNo(list.publicKey);
+		await program.methods.add(list.data.name, name, new BN(bounty), deadline === null ? null : new BN(deadline), uri, contentHash, pageNo)
 			.accounts({
 				list: list.publicKey,
 				listOwner: list.data.listOwner,
+				page: await pageAddress(list.publicKey, pageNo),
 				item: itemAccount.publicKey,
 				user: user.publicKey,
 				systemProgram: anchor.web3.SystemProgram.programId,
+				config: await configAddress(),
 			})
-			.signers([user, itemAccount])
+			.signers([user])
 			.rpc()
 
 		let [listData, itemData] = await Promise.all([
-			program.account.todoList.fetch(list.publicKey),
+			fetchList(list.publicKey),
 			program.account.listItem.fetch(itemAccount.publicKey),
 		]);
 
@@ -82,35 +188,357 @@ describe('todos', () => {
 		};
 	}
 
-	const cancelItem = async ({ list, item, itemCreator, user }) => {
+	const createTokenAccounts = async (mintAuthority: Keypair, owners: Keypair[], amount = 0) => {
+		const mint = await createMint(provider.connection, mintAuthority, mintAuthority.publicKey, null, 0);
+		const accounts = await Promise.all(owners.map(async (owner) => {
+			const account = await createAccount(provider.connection, owner, mint, owner.publicKey);
+			if (amount > 0) await mintTo(provider.connection, mintAuthority, mint, account, mintAuthority, amount);
+			return account;
+		}));
+		return { mint, accounts };
+	}
+
+	const getTokenBalance = async (pubkey: PublicKey) => {
+		let account = await getAccount(provider.connection, pubkey);
+		return Number(account.amount);
+	}
+
+	const escrowAccounts = async (item: PublicKey, destinations: PublicKey[]) => {
+		const [[escrow], [escrowAuthority]] = await Promise.all([
+			PublicKey.findProgramAddress(["escrow", item.toBuffer()], program.programId),
+			PublicKey.findProgramAddress(["escrow_authority", item.toBuffer()], program.programId),
+		]);
+
+		return [
+			{ pubkey: escrow, isSigner: false, isWritable: true },
+			{ pubkey: escrowAuthority, isSigner: false, isWritable: false },
+			{ pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+			...destinations.map((pubkey) => ({ pubkey, isSigner: false, isWritable: true })),
+		];
+	}
+
+	const addTokenItem = async ({ list, user, name, mint, userToken, amount }) => {
+		const itemAccount = await nextItemAccount(list.publicKey);
+		const [escrow, escrowAuthority] = await escrowAccounts(itemAccount.publicKey, []);
+		const pageNo = await nextPageNo(list.publicKey);
+		await program.methods.addToken(list.data.name, name, new BN(amount), null, null, EMPTY_HASH, pageNo)
+			.accounts({
+				list: list.publicKey,
+				listOwner: list.data.listOwner,
+				page: await pageAddress(list.publicKey, pageNo),
+				item: itemAccount.publicKey,
+				mint,
+				escrow: escrow.pubkey,
+				escrowAuthority: escrowAuthority.pubkey,
+				userToken,
+				user: user.publicKey,
+				config: await configAddress(),
+			})
+			.signers([user])
+			.rpc()
+
+		let [listData, itemData] = await Promise.all([
+			fetchList(list.publicKey),
+			program.account.listItem.fetch(itemAccount.publicKey),
+		]);
+
+		return {
+			list: { publicKey: list.publicKey, data: listData },
+			item: { publicKey: itemAccount.publicKey, data: itemData },
+			escrow: escrow.pubkey,
+		};
+	}
+
+	const moderatorAccount = async (list: PublicKey, moderator: PublicKey) => {
+		const [moderatorAccount] = await PublicKey.findProgramAddress([
+			"moderator",
+			list.toBuffer(),
+			moderator.toBuffer()
+		], program.programId);
+		return moderatorAccount;
+	}
+
+	const cancelItem = async ({ list, item, itemCreator, user, remainingAccounts = [] }) => {
 		await program.methods.cancel(list.data.name)
+			.accounts({
+				list: list.publicKey,
+				listOwner: list.data.listOwner,
+				...await removalAccounts(list.publicKey, item.publicKey),
+				item: item.publicKey,
+				itemCreator: itemCreator.publicKey,
+				user: user.publicKey,
+				moderator: await moderatorAccount(list.publicKey, user.publicKey),
+				config: await configAddress(),
+			})
+			.remainingAccounts(remainingAccounts)
+			.signers([user])
+			.rpc()
+
+		let listData = await fetchList(list.publicKey);
+		return { list: { publicKey: list.publicKey, data: listData } }
+	}
+
+	const expireItem = async ({ list, item, itemCreator, remainingAccounts = [] }) => {
+		await program.methods.expire(list.data.name)
+			.accounts({
+				list: list.publicKey,
+				listOwner: list.data.listOwner,
+				...await removalAccounts(list.publicKey, item.publicKey),
+				item: item.publicKey,
+				itemCreator: itemCreator.publicKey,
+				config: await configAddress(),
+			})
+			.remainingAccounts(remainingAccounts)
+			.rpc()
+
+		let listData = await fetchList(list.publicKey);
+		return { list: { publicKey: list.publicKey, data: listData } }
+	}
+
+	const closeList = async ({ list, listOwner, items = [] }) => {
+		const { pageCount } = await program.account.listHeader.fetch(list.publicKey);
+		const pages = await Promise.all([...Array(pageCount).keys()].map(async (pageNo) => {
+			const publicKey = await pageAddress(list.publicKey, pageNo);
+			const { payer } = await program.account.listPage.fetch(publicKey);
+			return [
+				{ pubkey: publicKey, isSigner: false, isWritable: true },
+				{ pubkey: payer, isSigner: false, isWritable: true },
+			];
+		}));
+
+		await program.methods.closeList(list.data.name)
+			.accounts({
+				list: list.publicKey,
+				listOwner: listOwner.publicKey,
+				config: await configAddress(),
+			})
+			.remainingAccounts([
+				...pages.flat(),
+				...items.flatMap(({ item, itemCreator }) => [
+					{ pubkey: item.publicKey, isSigner: false, isWritable: true },
+					{ pubkey: itemCreator.publicKey, isSigner: false, isWritable: true },
+				]),
+			])
+			.signers([listOwner])
+			.rpc()
+	}
+
+	const closePage = async ({ list, pageNo }) => {
+		const publicKey = await pageAddress(list.publicKey, pageNo);
+		const { payer } = await program.account.listPage.fetch(publicKey);
+		await program.methods.closePage(list.data.name)
+			.accounts({
+				list: list.publicKey,
+				page: publicKey,
+				payer,
+				config: await configAddress(),
+			})
+			.rpc()
+	}
+
+	const resizeList = async ({ list, listOwner, capacity }) => {
+		await program.methods.resizeList(list.data.name, capacity)
+			.accounts({
+				list: list.publicKey,
+				listOwner: listOwner.publicKey,
+				config: await configAddress(),
+			})
+			.signers([listOwner])
+			.rpc()
+
+		let listData = await fetchList(list.publicKey);
+		return { publicKey: list.publicKey, data: listData };
+	}
+
+	const proposeOwner = async ({ list, listOwner, newOwner }) => {
+		await program.methods.proposeOwner(list.data.name, newOwner)
+			.accounts({
+				list: list.publicKey,
+				listOwner: listOwner.publicKey,
+				config: await configAddress(),
+			})
+			.signers([listOwner])
+			.rpc()
+	}
+
+	const acceptOwner = async ({ list, newOwner }) => {
+		await program.methods.acceptOwner(list.data.name)
+			.accounts({
+				list: list.publicKey,
+				newOwner: newOwner.publicKey,
+				config: await configAddress(),
+			})
+			.signers([newOwner])
+			.rpc()
+
+		let listData = await fetchList(list.publicKey);
+		return { publicKey: list.publicKey, data: listData };
+	}
+
+	const setModerator = async ({ list, listOwner, moderator, canCancel, canFinish }) => {
+		const moderatorKey = await moderatorAccount(list.publicKey, moderator.publicKey);
+		await program.methods.setModerator(list.data.name, moderator.publicKey, canCancel, canFinish)
+			.accounts({
+				list: list.publicKey,
+				moderator: moderatorKey,
+				listOwner: listOwner.publicKey,
+				config: await configAddress(),
+			})
+			.signers([listOwner])
+			.rpc()
+
+		return program.account.moderator.fetch(moderatorKey);
+	}
+
+	const removeModerator = async ({ list, listOwner, moderator }) => {
+		await program.methods.removeModerator(list.data.name, moderator.publicKey)
+			.accounts({
+				list: list.publicKey,
+				moderator: await moderatorAccount(list.publicKey, moderator.publicKey),
+				listOwner: listOwner.publicKey,
+				config: await configAddress(),
+			})
+			.signers([listOwner])
+			.rpc()
+	}
+
+	const editItem = async ({ list, item, itemCreator, name, uri = null, contentHash = EMPTY_HASH }) => {
+		await program.methods.editItem(list.data.name, name, uri, contentHash)
 			.accounts({
 				list: list.publicKey,
 				listOwner: list.data.listOwner,
 				item: item.publicKey,
 				itemCreator: itemCreator.publicKey,
+				config: await configAddress(),
+			})
+			.signers([itemCreator])
+			.rpc()
+
+		return program.account.listItem.fetch(item.publicKey);
+	}
+
+	const unfinishItem = async ({ list, item, user }) => {
+		await program.methods.unfinish(list.data.name)
+			.accounts({
+				list: list.publicKey,
+				listOwner: list.data.listOwner,
+				item: item.publicKey,
+				user: user.publicKey,
+				moderator: await moderatorAccount(list.publicKey, user.publicKey),
+				config: await configAddress(),
+			})
+			.signers([user])
+			.rpc()
+
+		return program.account.listItem.fetch(item.publicKey);
+	}
+
+	const claimAfterTimeout = async ({ list, listOwner, item, remainingAccounts = [] }) => {
+		await program.methods.claimAfterTimeout(list.data.name)
+			.accounts({
+				list: list.publicKey,
+				listOwner: listOwner.publicKey,
+				...await removalAccounts(list.publicKey, item.publicKey),
+				item: item.publicKey,
+				...await feeAccounts(listOwner.publicKey),
+			})
+			.remainingAccounts(remainingAccounts)
+			.signers([listOwner])
+			.rpc()
+
+		let listData = await fetchList(list.publicKey);
+		return { list: { publicKey: list.publicKey, data: listData } }
+	}
+
+	const openDispute = async ({ list, item, user }) => {
+		await program.methods.openDispute(list.data.name)
+			.accounts({
+				list: list.publicKey,
+				listOwner: list.data.listOwner,
+				item: item.publicKey,
 				user: user.publicKey,
+				config: await configAddress(),
 			})
 			.signers([user])
 			.rpc()
 
-		let listData = await program.account.todoList.fetch(list.publicKey);
+		return program.account.listItem.fetch(item.publicKey);
+	}
+
+	const resolveDispute = async ({ list, item, itemCreator, arbiter, ownerShareBps, remainingAccounts = [] }) => {
+		await program.methods.resolveDispute(list.data.name, ownerShareBps)
+			.accounts({
+				list: list.publicKey,
+				listOwner: list.data.listOwner,
+				...await removalAccounts(list.publicKey, item.publicKey),
+				item: item.publicKey,
+				itemCreator: itemCreator.publicKey,
+				arbiter: arbiter.publicKey,
+				...await feeAccounts(list.data.listOwner),
+			})
+			.remainingAccounts(remainingAccounts)
+			.signers([arbiter])
+			.rpc()
+
+		let listData = await fetchList(list.publicKey);
 		return { list: { publicKey: list.publicKey, data: listData } }
 	}
 
-	const finishItem = async ({ list, listOwner, item, user, expectAccountClosed }) => {
+	const contributionAccounts = async (item: PublicKey, funders: PublicKey[]) => {
+		const contributions = await Promise.all(funders.map((funder) => {
+			return PublicKey.findProgramAddress(["contribution", item.toBuffer(), funder.toBuffer()], program.programId);
+		}));
+
+		return contributions.flatMap(([contribution], i) => [
+			{ pubkey: contribution, isSigner: false, isWritable: true },
+			{ pubkey: funders[i], isSigner: false, isWritable: true },
+		]);
+	}
+
+	const fundItem = async ({ list, item, funder, amount }) => {
+		const [contribution] = await PublicKey.findProgramAddress([
+			"contribution",
+			item.publicKey.toBuffer(),
+			funder.publicKey.toBuffer()
+		], program.programId);
+
+		await program.methods.fund(list.data.name, new BN(amount))
+			.accounts({
+				list: list.publicKey,
+				listOwner: list.data.listOwner,
+				item: item.publicKey,
+				contribution,
+				funder: funder.publicKey,
+				config: await configAddress(),
+			})
+			.signers([funder])
+			.rpc()
+
+		let [itemData, contributionData] = await Promise.all([
+			program.account.listItem.fetch(item.publicKey),
+			program.account.contribution.fetch(contribution),
+		]);
+
+		return { item: { publicKey: item.publicKey, data: itemData }, contribution: contributionData };
+	}
+
+	const finishItem = async ({ list, listOwner, item, user, expectAccountClosed, remainingAccounts = [] }) => {
 		await program.methods.finish(list.data.name)
 			.accounts({
 				list: list.publicKey,
 				listOwner: listOwner.publicKey,
+				...await removalAccounts(list.publicKey, item.publicKey),
 				item: item.publicKey,
 				user: user.publicKey,
+				moderator: await moderatorAccount(list.publicKey, user.publicKey),
+				...await feeAccounts(listOwner.publicKey),
 			})
+			.remainingAccounts(remainingAccounts)
 			.signers([user])
 			.rpc()
 
 		let [listData, itemData] = await Promise.all([
-			program.account.todoList.fetch(list.publicKey),
+			fetchList(list.publicKey),
 			expectAccountClosed ? null : await program.account.listItem.fetch(item.publicKey),
 		]);
 
@@ -135,6 +563,8 @@ describe('todos', () => {
 			expect(list.data.listOwner.toString(), 'List owner is set').equals(owner.publicKey.toString());
 			expect(list.data.name, 'List name is set').equals('A list');
 			expect(list.data.lines.length, 'List has no items').equals(0);
+			expect(list.data.arbiter, 'List has no arbiter').equals(null);
+			expect(list.data.bump, 'Canonical bump is stored').equals(list.bump);
 		});
 		it('can create another list for a user with an active list', async () => {
 			const owner = provider.wallet;
@@ -143,6 +573,59 @@ describe('todos', () => {
 			expect(list.data.name, 'List name is set').equals('Another list');
 			expect(list.data.lines.length, 'List has no items').equals(0);
 		});
+		it('can create lists whose names share the first 32 bytes', async () => {
+			const owner = await createUser();
+			const q1 = await createList(owner, 'Quarterly infrastructure roadmap - Q1');
+			const q2 = await createList(owner, 'Quarterly infrastructure roadmap - Q2');
+			expect(q1.publicKey.toString(), 'Hashed seeds use the full name').not.equals(q2.publicKey.toString());
+			expect(q2.data.name, 'List name is set').equals('Quarterly infrastructure roadmap - Q2');
+		});
+		it('can create a list with the legacy truncated seed', async () => {
+			const owner = await createUser();
+			const list = await createList(owner, 'A legacy list', 16, null, null, TRUNCATED);
+			expect(list.data.seedScheme, 'Seed scheme is stored').equals(0);
+			await addItem({ list, user: owner, name: 'An item', bounty: LPS });
+		});
+		it('cannot create a list with an invalid name', async () => {
+			const owner = await createUser();
+			const invalidNames = [
+				{ name: '', seedScheme: HASHED, code: "ListNameEmpty" },
+				{ name: 'a'.repeat(65), seedScheme: HASHED, code: "ListNameTooLong" },
+				// "ä" takes two bytes, the 32 byte seed would end in the middle of it
+				{ name: 'a'.repeat(31) + 'ä', seedScheme: TRUNCATED, code: "ListNameSplitsCharacter" },
+			];
+
+			for (const { name, seedScheme, code } of invalidNames) {
+				try {
+					await createList(owner, name, 16, null, null, seedScheme);
+					expect.fail(`Creating a list named "${name}" should fail`);
+				} catch (e) {
+					expect(e.error.errorCode.code).equals(code);
+				}
+			}
+		});
+
+		it('cannot create a list with a review window but no arbiter', async () => {
+			const owner = await createUser();
+
+			try {
+				await createList(owner, 'list', 16, null, 3600);
+				expect.fail('A review window without arbiter should fail');
+			} catch (e) {
+				expect(e.error.errorCode.code).equals("ReviewWindowNeedsArbiter");
+			}
+		});
+
+		it('cannot create a list with the owner as arbiter', async () => {
+			const owner = await createUser();
+
+			try {
+				await createList(owner, 'list', 16, owner.publicKey);
+				expect.fail('Arbitrating their own list should fail');
+			} catch (e) {
+				expect(e.error.errorCode.code).equals("OwnerIsArbiter");
+			}
+		});
 	});
 
 	describe('add items', () => {
@@ -158,10 +641,17 @@ describe('todos', () => {
 			expect(result.item.data.creatorFinished, 'creator_finished is false').equals(false);
 			expect(result.item.data.listOwnerFinished, 'list_owner_finished is false').equals(false);
 			expect(result.item.data.name, 'Name is set').equals('Do something');
+			expect(result.item.data.bountyMint, 'Bounty is paid in lamports').equals(null);
+			expect(result.item.data.bountyAmount.toNumber(), 'Bounty amount is recorded').equals(1 * LPS);
+			expect(result.item.data.deadline, 'Item has no deadline').equals(null);
+			expect(result.item.data.uri, 'Item has no description').equals(null);
+			expect(result.item.data.index.toNumber(), 'Item index is set').equals(0);
 			expect(await getAccountBalance(result.item.publicKey), 'List account balance').equals(1 * LPS);
 
+			// The first item opens the first page, its adder pays the rent of the page
+			const pageRent = await getAccountBalance(await pageAddress(list.publicKey, 0));
 			const userNewBalance = await getAccountBalance(adder.publicKey);
-			expectBalance(adderStartingBalance - userNewBalance, LPS, 'Number of lamports removed from adder is equal to bounty');
+			expectBalance(adderStartingBalance - userNewBalance, LPS + pageRent, 'Number of lamports removed from adder is equal to bounty and page rent');
 
 			// Add item from another use
 			const resultTwo = await addItem({ list, user: otherUser, name: 'Do something more', bounty: 1 * LPS });
@@ -170,6 +660,11 @@ describe('todos', () => {
 			// Add item from user who already added an item
 			const resultThree = await addItem({ list, user: adder, name: 'Another item', bounty: 1 * LPS });
 			expect(resultThree.list.data.lines, 'Item is added').deep.equals([result.item.publicKey, resultTwo.item.publicKey, resultThree.item.publicKey]);
+
+			// Every item can be derived from the list alone
+			expect(resultThree.list.data.itemCount.toNumber(), 'Item count is incremented').equals(3);
+			const derived = await Promise.all([0, 1, 2].map((i) => itemAddress(list.publicKey, i)));
+			expect(derived, 'Items are derived from the list and index').deep.equals(resultThree.list.data.lines);
 		});
 
 		it('cannot add items when the list is full', async () => {
@@ -178,14 +673,15 @@ describe('todos', () => {
 			const MAX_LIST_SIZE = 4;
 			const list = await createList(owner, 'list', MAX_LIST_SIZE);
 
-			await Promise.all(new Array(MAX_LIST_SIZE).fill(0).map((_, i) => {
-				return addItem({
+			// Items are derived from the list's item count, so they are added one after another
+			for (let i = 0; i < MAX_LIST_SIZE; i++) {
+				await addItem({
 					list,
 					user: owner,
 					name: `Filler item ${i}`,
 					bounty: 1 * LPS,
 				});
-			}));
+			}
 
 			const adderStartingBalance = await getAccountBalance(owner.publicKey);
 
@@ -261,6 +757,52 @@ describe('todos', () => {
 			expect(cancelResult.list.data.lines, 'Cancel removes item from list').deep.equals([]);
 		});
 
+		it('can cancel item: last item moves into the freed slot', async () => {
+			const owner = await createUser();
+			const list = await createList(owner, 'list');
+			const first = await addItem({ list, user: owner, name: 'First item', bounty: LPS });
+			const second = await addItem({ list, user: owner, name: 'Second item', bounty: LPS });
+			const third = await addItem({ list, user: owner, name: 'Third item', bounty: LPS });
+
+			expect(third.item.data.slot, 'Item stores its slot').equals(2);
+
+			const cancelResult = await cancelItem({ list, item: first.item, itemCreator: owner, user: owner });
+			expect(cancelResult.list.data.lines, 'Last item takes the slot of the cancelled one').deep.equals([third.item.publicKey, second.item.publicKey]);
+			const moved = await program.account.listItem.fetch(third.item.publicKey);
+			expect(moved.slot, 'Moved item stores its new slot').equals(0);
+
+			const secondCancel = await cancelItem({ list, item: third.item, itemCreator: owner, user: owner });
+			expect(secondCancel.list.data.lines, 'Moved item is removed from its new slot').deep.equals([second.item.publicKey]);
+		});
+
+		it('cannot cancel item: wrong moved item', async () => {
+			const owner = await createUser();
+			const list = await createList(owner, 'list');
+			const first = await addItem({ list, user: owner, name: 'First item', bounty: LPS });
+			const second = await addItem({ list, user: owner, name: 'Second item', bounty: LPS });
+			await addItem({ list, user: owner, name: 'Third item', bounty: LPS });
+
+			try {
+				await program.methods.cancel(list.data.name)
+					.accounts({
+						list: list.publicKey,
+						listOwner: owner.publicKey,
+						page: await pageAddress(list.publicKey, 0),
+						item: first.item.publicKey,
+						movedItem: second.item.publicKey,
+						itemCreator: owner.publicKey,
+						user: owner.publicKey,
+						moderator: await moderatorAccount(list.publicKey, owner.publicKey),
+						config: await configAddress(),
+					})
+					.signers([owner])
+					.rpc();
+				expect.fail('Cancelling with another item than the last one on the page should fail');
+			} catch (e) {
+				expect(e.error.errorCode.code).equals("WrongMovedItem");
+			}
+		});
+
 		it('can cancel item: item creator', async () => {
 			const [owner, adder] = await createUsers(2);
 
@@ -291,6 +833,68 @@ describe('todos', () => {
 			expect(cancelResult.list.data.lines, 'Cancel removes item from list').deep.equals([]);
 		});
 
+		it('cannot cancel item: item creator after the owner finished', async () => {
+			const [owner, adder, arbiter] = await createUsers(3);
+			const list = await createList(owner, 'list', 16, arbiter.publicKey);
+			const { item } = await addItem({ list, user: adder, name: 'An item', bounty: LPS });
+			await finishItem({ list, listOwner: owner, item, user: owner, expectAccountClosed: false });
+
+			try {
+				await cancelItem({ list, item, itemCreator: adder, user: adder });
+				expect.fail('Cancelling a delivered item should fail');
+			} catch (e) {
+				expect(e.error.errorCode.code).equals("OwnerAlreadyFinished");
+			}
+
+			expect(await getAccountBalance(item.publicKey), 'Item balance is unchanged after failed cancel').equals(LPS);
+
+			const adderBalance = await getAccountBalance(adder.publicKey);
+			await cancelItem({ list, item, itemCreator: adder, user: owner });
+			expect(await getAccountBalance(adder.publicKey), 'List owner may still cancel').equals(adderBalance + LPS);
+		});
+
+		it('cannot expire item after the owner finished', async () => {
+			const [owner, adder, arbiter] = await createUsers(3);
+			const list = await createList(owner, 'list', 16, arbiter.publicKey);
+			const { item } = await addItem({ list, user: adder, name: 'An item', bounty: LPS, deadline: now() + 2 });
+			await finishItem({ list, listOwner: owner, item, user: owner, expectAccountClosed: false });
+
+			await sleep(4000);
+
+			try {
+				await expireItem({ list, item, itemCreator: adder });
+				expect.fail('Expiring a delivered item should fail');
+			} catch (e) {
+				expect(e.error.errorCode.code).equals("OwnerAlreadyFinished");
+			}
+
+			expect(await getAccountBalance(item.publicKey), 'Item balance is unchanged after failed expire').equals(LPS);
+		});
+
+		it('can cancel item: item creator after the owner finished in a list without arbiter', async () => {
+			const [owner, adder] = await createUsers(2);
+			const list = await createList(owner, 'list');
+			const { item } = await addItem({ list, user: adder, name: 'An item', bounty: LPS });
+			await finishItem({ list, listOwner: owner, item, user: owner, expectAccountClosed: false });
+
+			const adderBalance = await getAccountBalance(adder.publicKey);
+			await cancelItem({ list, item, itemCreator: adder, user: adder });
+			expectBalance(await getAccountBalance(adder.publicKey), adderBalance + LPS, 'Without arbiter the creator may still cancel');
+		});
+
+		it('can expire item after the owner finished in a list without arbiter', async () => {
+			const [owner, adder] = await createUsers(2);
+			const list = await createList(owner, 'list');
+			const { item } = await addItem({ list, user: adder, name: 'An item', bounty: LPS, deadline: now() + 2 });
+			await finishItem({ list, listOwner: owner, item, user: owner, expectAccountClosed: false });
+
+			await sleep(4000);
+
+			const adderBalance = await getAccountBalance(adder.publicKey);
+			await expireItem({ list, item, itemCreator: adder });
+			expect(await getAccountBalance(adder.publicKey), 'Without arbiter the deadline still refunds the creator').equals(adderBalance + LPS);
+		});
+
 		it('cannot cancel item: other user', async () => {
 			const [owner, adder, otherUser] = await createUsers(3);
 
@@ -325,7 +929,7 @@ describe('todos', () => {
 			const adderBalanceAfterCancel = await getAccountBalance(adder.publicKey);
 			expect(adderBalanceAfterCancel, 'Failed cancel does not change adder balance').equals(adderBalanceAfterAdd);
 
-			let listData = await program.account.todoList.fetch(list.publicKey);
+			let listData = await fetchList(list.publicKey);
 			expect(listData.lines, 'Item is still in list after failed cancel').deep.equals([result.item.publicKey]);
 
 			const itemBalance = await getAccountBalance(result.item.publicKey);
@@ -369,6 +973,7 @@ describe('todos', () => {
 				bounty: LPS,
 				name: 'An item',
 			});
+			await addItem({ list: list2, user: adder, bounty: LPS, name: 'An item' });
 
 			try {
 				await cancelItem({
@@ -508,13 +1113,14 @@ describe('todos', () => {
 			]);
 
 			const bounty = 5 * LPS;
-			// TODO: create item with same name in other list and test finishing
 			const { item } = await addItem({
 				list: list1,
 				user: adder,
 				bounty,
 				name: 'An item',
 			});
+			// The adder also pays the rent of the first page of both lists
+			await addItem({ list: list2, user: adder, bounty: LPS, name: 'An item' });
 
 			try {
 				await finishItem({
@@ -556,7 +1162,7 @@ describe('todos', () => {
 
 				expect.fail('Finish by other user should have failed');
 			} catch (e) {
-				expect(e.error.errorCode.code).equals("ConstraintSeeds");
+				expect(e.error.errorCode.code).equals("WrongListOwner");
 			}
 
 			expect(await getAccountBalance(item.publicKey), 'Item balance did not change').equal(bounty);
@@ -612,6 +1218,995 @@ describe('todos', () => {
 
 			expectBalance(await getAccountBalance(owner.publicKey), ownerInitial + bounty, 'Bounty transferred to owner just once');
 		});
-	});
-});
 
+		it('can unfinish an item: each side clears only their mark', async () => {
+			const [owner, adder, arbiter] = await createUsers(3);
+			const list = await createList(owner, 'list', 16, arbiter.publicKey, 3600);
+			const { item } = await addItem({ list, user: adder, name: 'An item', bounty: LPS });
+
+			await finishItem({ list, item, user: owner, listOwner: owner, expectAccountClosed: false });
+			let itemData = await unfinishItem({ list, item, user: owner });
+			expect(itemData.listOwnerFinished, 'Owner finish flag is cleared').equals(false);
+			expect(itemData.ownerFinishedAt, 'Review window is reset').equals(null);
+
+			await finishItem({ list, item, user: adder, listOwner: owner, expectAccountClosed: false });
+			try {
+				await unfinishItem({ list, item, user: owner });
+				expect.fail(`Clearing the creator's mark should fail`);
+			} catch (e) {
+				expect(e.error.errorCode.code).equals("NotMarkedFinished");
+			}
+
+			itemData = await unfinishItem({ list, item, user: adder });
+			expect(itemData.creatorFinished, 'Creator finish flag is cleared').equals(false);
+		});
+
+		it('cannot unfinish an item: other user', async () => {
+			const [owner, adder, otherUser] = await createUsers(3);
+			const list = await createList(owner, 'list');
+			const { item } = await addItem({ list, user: adder, name: 'An item', bounty: LPS });
+			await finishItem({ list, item, user: owner, listOwner: owner, expectAccountClosed: false });
+
+			try {
+				await unfinishItem({ list, item, user: otherUser });
+				expect.fail(`Clearing another user's mark should fail`);
+			} catch (e) {
+				expect(e.error.errorCode.code).equals("FinishPermissions");
+			}
+		});
+	});
+
+	describe('token bounties', () => {
+		it('can add an item with a token bounty', async () => {
+			const [owner, adder] = await createUsers(2);
+			const { mint, accounts: [adderToken] } = await createTokenAccounts(adder, [adder], 1000);
+
+			const list = await createList(owner, 'list');
+			const result = await addTokenItem({ list, user: adder, name: 'An item', mint, userToken: adderToken, amount: 400 });
+
+			expect(result.list.data.lines, 'Item is added').deep.equals([result.item.publicKey]);
+			expect(result.item.data.bountyMint.toString(), 'Item records the bounty mint').equals(mint.toString());
+			expect(result.item.data.bountyAmount.toNumber(), 'Item records the bounty amount').equals(400);
+			expect(await getTokenBalance(result.escrow), 'Escrow holds the bounty').equals(400);
+			expect(await getTokenBalance(adderToken), 'Bounty is removed from adder').equals(600);
+		});
+
+		it('can cancel a token item: tokens return to the item creator', async () => {
+			const [owner, adder] = await createUsers(2);
+			const { mint, accounts: [adderToken] } = await createTokenAccounts(adder, [adder], 1000);
+
+			const list = await createList(owner, 'list');
+			const { item, escrow } = await addTokenItem({ list, user: adder, name: 'An item', mint, userToken: adderToken, amount: 400 });
+
+			const cancelResult = await cancelItem({
+				list,
+				item,
+				itemCreator: adder,
+				user: owner,
+				remainingAccounts: await escrowAccounts(item.publicKey, [adderToken]),
+			});
+
+			expect(cancelResult.list.data.lines, 'Cancel removes item from list').deep.equals([]);
+			expect(await getTokenBalance(adderToken), 'Cancel returns tokens to adder').equals(1000);
+			expect(await getAccountBalance(escrow), 'Escrow is closed').equals(0);
+			expect(await getAccountBalance(item.publicKey), 'Item is closed').equals(0);
+		});
+
+		it('cannot cancel a token item without the escrow accounts', async () => {
+			const [owner, adder] = await createUsers(2);
+			const { mint, accounts: [adderToken] } = await createTokenAccounts(adder, [adder], 1000);
+
+			const list = await createList(owner, 'list');
+			const { item, escrow } = await addTokenItem({ list, user: adder, name: 'An item', mint, userToken: adderToken, amount: 400 });
+
+			try {
+				await cancelItem({ list, item, itemCreator: adder, user: adder });
+				expect.fail('Cancelling without the escrow should fail');
+			} catch (e) {
+				expect(e.error.errorCode.code).equals("MissingEscrowAccounts");
+			}
+
+			expect(await getTokenBalance(escrow), 'Escrow balance is unchanged').equals(400);
+		});
+
+		it('can finish a token item: tokens are paid to the list owner', async () => {
+			const [owner, adder] = await createUsers(2);
+			const { mint, accounts: [adderToken, ownerToken] } = await createTokenAccounts(adder, [adder, owner], 1000);
+
+			const list = await createList(owner, 'list');
+			const { item, escrow } = await addTokenItem({ list, user: adder, name: 'An item', mint, userToken: adderToken, amount: 400 });
+
+			await finishItem({ list, item, user: owner, listOwner: owner, expectAccountClosed: false });
+
+			try {
+				await finishItem({
+					list,
+					item,
+					user: adder,
+					listOwner: owner,
+					expectAccountClosed: true,
+					remainingAccounts: await escrowAccounts(item.publicKey, [adderToken]),
+				});
+				expect.fail('Paying the bounty to the creator should fail');
+			} catch (e) {
+				expect(e.error.errorCode.code).equals("WrongTokenAccount");
+			}
+
+			const finishResult = await finishItem({
+				list,
+				item,
+				user: adder,
+				listOwner: owner,
+				expectAccountClosed: true,
+				remainingAccounts: await escrowAccounts(item.publicKey, [ownerToken]),
+			});
+
+			expect(finishResult.list.data.lines, 'Item removed from list after both finish').deep.equals([]);
+			expect(await getTokenBalance(ownerToken), 'Bounty transferred to owner').equals(1400);
+			expect(await getAccountBalance(escrow), 'Escrow is closed').equals(0);
+		});
+	});
+
+	describe('deadlines', () => {
+		it('cannot add an item with a deadline in the past', async () => {
+			const [owner, adder] = await createUsers(2);
+			const list = await createList(owner, 'list');
+
+			try {
+				await addItem({ list, user: adder, name: 'An item', bounty: LPS, deadline: now() - 60 });
+				expect.fail('Adding with a past deadline should fail');
+			} catch (e) {
+				expect(e.error.errorCode.code).equals("DeadlineInPast");
+			}
+		});
+
+		it('cannot expire an item without a deadline', async () => {
+			const [owner, adder] = await createUsers(2);
+			const list = await createList(owner, 'list');
+			const { item } = await addItem({ list, user: adder, name: 'An item', bounty: LPS });
+
+			try {
+				await expireItem({ list, item, itemCreator: adder });
+				expect.fail('Expiring without a deadline should fail');
+			} catch (e) {
+				expect(e.error.errorCode.code).equals("NoDeadline");
+			}
+		});
+
+		it('cannot expire an item before its deadline', async () => {
+			const [owner, adder] = await createUsers(2);
+			const list = await createList(owner, 'list');
+			const { item } = await addItem({ list, user: adder, name: 'An item', bounty: LPS, deadline: now() + 3600 });
+
+			try {
+				await expireItem({ list, item, itemCreator: adder });
+				expect.fail('Expiring before the deadline should fail');
+			} catch (e) {
+				expect(e.error.errorCode.code).equals("DeadlineNotReached");
+			}
+
+			expect(await getAccountBalance(item.publicKey), 'Item balance is unchanged').equals(LPS);
+		});
+
+		it('can expire an item after its deadline: bounty returns to the item creator', async () => {
+			const [owner, adder] = await createUsers(2);
+			const list = await createList(owner, 'list');
+			const { item } = await addItem({ list, user: adder, name: 'An item', bounty: LPS, deadline: now() + 2 });
+			const adderBalanceAfterAdd = await getAccountBalance(adder.publicKey);
+
+			await sleep(4000);
+
+			// Expired by the wallet, anyone may call expire
+			const expireResult = await expireItem({ list, item, itemCreator: adder });
+
+			expect(expireResult.list.data.lines, 'Expire removes item from list').deep.equals([]);
+			expect(await getAccountBalance(adder.publicKey), 'Expire returns bounty to adder').equals(adderBalanceAfterAdd + LPS);
+		});
+	});
+
+	describe('review window', () => {
+		it('cannot claim an item in a list without review window', async () => {
+			const [owner, adder] = await createUsers(2);
+			const list = await createList(owner, 'list');
+			const { item } = await addItem({ list, user: adder, name: 'An item', bounty: LPS });
+			await finishItem({ list, listOwner: owner, item, user: owner, expectAccountClosed: false });
+
+			try {
+				await claimAfterTimeout({ list, listOwner: owner, item });
+				expect.fail('Claiming without a review window should fail');
+			} catch (e) {
+				expect(e.error.errorCode.code).equals("NoReviewWindow");
+			}
+		});
+
+		it('cannot claim an item the owner did not finish', async () => {
+			const [owner, adder, arbiter] = await createUsers(3);
+			const list = await createList(owner, 'list', 16, arbiter.publicKey, 2);
+			const { item } = await addItem({ list, user: adder, name: 'An item', bounty: LPS });
+
+			try {
+				await claimAfterTimeout({ list, listOwner: owner, item });
+				expect.fail('Claiming an unfinished item should fail');
+			} catch (e) {
+				expect(e.error.errorCode.code).equals("OwnerNotFinished");
+			}
+		});
+
+		it('cannot claim an item before the review window passed', async () => {
+			const [owner, adder, arbiter] = await createUsers(3);
+			const list = await createList(owner, 'list', 16, arbiter.publicKey, 3600);
+			const { item } = await addItem({ list, user: adder, name: 'An item', bounty: LPS });
+			await finishItem({ list, listOwner: owner, item, user: owner, expectAccountClosed: false });
+
+			try {
+				await claimAfterTimeout({ list, listOwner: owner, item });
+				expect.fail('Claiming during the review window should fail');
+			} catch (e) {
+				expect(e.error.errorCode.code).equals("ReviewWindowOpen");
+			}
+
+			expect(await getAccountBalance(item.publicKey), 'Item balance is unchanged').equals(LPS);
+		});
+
+		it('can claim an item after the review window: bounty is paid to the list owner', async () => {
+			const [owner, adder, arbiter] = await createUsers(3);
+			const list = await createList(owner, 'list', 16, arbiter.publicKey, 2);
+			const { item } = await addItem({ list, user: adder, name: 'An item', bounty: LPS });
+			const finishResult = await finishItem({ list, listOwner: owner, item, user: owner, expectAccountClosed: false });
+			expect(finishResult.item.data.ownerFinishedAt, 'Owner finish time is recorded').not.equals(null);
+			const ownerBalance = await getAccountBalance(owner.publicKey);
+
+			await sleep(4000);
+			const claimResult = await claimAfterTimeout({ list, listOwner: owner, item });
+
+			expect(claimResult.list.data.lines, 'Claim removes item from list').deep.equals([]);
+			expect(await getAccountBalance(item.publicKey), 'Item is closed').equals(0);
+			expectBalance(await getAccountBalance(owner.publicKey), ownerBalance + LPS, 'Bounty transferred to owner');
+		});
+	});
+
+	describe('disputes', () => {
+		it('cannot dispute an item in a list without arbiter', async () => {
+			const [owner, adder] = await createUsers(2);
+			const list = await createList(owner, 'list');
+			const { item } = await addItem({ list, user: adder, name: 'An item', bounty: LPS });
+
+			try {
+				await openDispute({ list, item, user: owner });
+				expect.fail('Disputing without an arbiter should fail');
+			} catch (e) {
+				expect(e.error.errorCode.code).equals("NoArbiter");
+			}
+		});
+
+		it('cannot cancel or finish a disputed item', async () => {
+			const [owner, adder, arbiter] = await createUsers(3);
+			const list = await createList(owner, 'list', 16, arbiter.publicKey);
+			const { item } = await addItem({ list, user: adder, name: 'An item', bounty: LPS });
+
+			await finishItem({ list, item, user: owner, listOwner: owner, expectAccountClosed: false });
+			const itemData = await openDispute({ list, item, user: owner });
+			expect(itemData.disputed, 'Item is disputed').equals(true);
+
+			try {
+				await cancelItem({ list, item, itemCreator: adder, user: owner });
+				expect.fail('Cancelling a disputed item should fail');
+			} catch (e) {
+				expect(e.error.errorCode.code).equals("ItemDisputed");
+			}
+
+			try {
+				await finishItem({ list, item, user: adder, listOwner: owner, expectAccountClosed: true });
+				expect.fail('Finishing a disputed item should fail');
+			} catch (e) {
+				expect(e.error.errorCode.code).equals("ItemDisputed");
+			}
+
+			expect(await getAccountBalance(item.publicKey), 'Item balance is unchanged').equals(LPS);
+		});
+
+		it('cannot resolve a dispute: other user', async () => {
+			const [owner, adder, arbiter] = await createUsers(3);
+			const list = await createList(owner, 'list', 16, arbiter.publicKey);
+			const { item } = await addItem({ list, user: adder, name: 'An item', bounty: LPS });
+			await openDispute({ list, item, user: adder });
+
+			try {
+				await resolveDispute({ list, item, itemCreator: adder, arbiter: owner, ownerShareBps: 10000 });
+				expect.fail('Resolving by the list owner should fail');
+			} catch (e) {
+				expect(e.error.errorCode.code).equals("NotArbiter");
+			}
+		});
+
+		it('can resolve a dispute: arbiter splits the bounty', async () => {
+			const [owner, adder, arbiter] = await createUsers(3);
+			const list = await createList(owner, 'list', 16, arbiter.publicKey);
+			const { item } = await addItem({ list, user: adder, name: 'An item', bounty: 2 * LPS });
+			await openDispute({ list, item, user: owner });
+
+			const ownerBalance = await getAccountBalance(owner.publicKey);
+			const adderBalance = await getAccountBalance(adder.publicKey);
+
+			const result = await resolveDispute({ list, item, itemCreator: adder, arbiter, ownerShareBps: 7500 });
+
+			expect(result.list.data.lines, 'Resolving removes item from list').deep.equals([]);
+			expect(await getAccountBalance(item.publicKey), 'Item is closed').equals(0);
+			expect(await getAccountBalance(owner.publicKey), 'Owner receives their share').equals(ownerBalance + 1.5 * LPS);
+			expect(await getAccountBalance(adder.publicKey), 'Creator receives the rest').equals(adderBalance + 0.5 * LPS);
+		});
+
+		it('can resolve a dispute over a token bounty', async () => {
+			const [owner, adder, arbiter] = await createUsers(3);
+			const { mint, accounts: [adderToken, ownerToken] } = await createTokenAccounts(adder, [adder, owner], 1000);
+			const list = await createList(owner, 'list', 16, arbiter.publicKey);
+			const { item } = await addTokenItem({ list, user: adder, name: 'An item', mint, userToken: adderToken, amount: 400 });
+			await openDispute({ list, item, user: adder });
+
+			await resolveDispute({
+				list,
+				item,
+				itemCreator: adder,
+				arbiter,
+				ownerShareBps: 2500,
+				remainingAccounts: await escrowAccounts(item.publicKey, [ownerToken, adderToken]),
+			});
+
+			expect(await getTokenBalance(ownerToken), 'Owner receives their share').equals(1100);
+			expect(await getTokenBalance(adderToken), 'Creator receives the rest').equals(900);
+		});
+	});
+
+	describe('crowdfunding', () => {
+		it('can fund an existing item from multiple users', async () => {
+			const [owner, adder, funder] = await createUsers(3);
+			const list = await createList(owner, 'list');
+			const { item } = await addItem({ list, user: adder, name: 'An item', bounty: LPS });
+
+			await fundItem({ list, item, funder, amount: LPS });
+			const result = await fundItem({ list, item, funder, amount: LPS });
+			expect(result.contribution.amount.toNumber(), 'Contributions of a funder add up').equals(2 * LPS);
+			expect(result.item.data.funders, 'Repeated funding counts the funder once').equals(1);
+
+			const resultTwo = await fundItem({ list, item, funder: owner, amount: LPS });
+			expect(resultTwo.item.data.funders, 'Another funder is counted').equals(2);
+			expect(resultTwo.item.data.fundedAmount.toNumber(), 'Funded amount is recorded').equals(3 * LPS);
+			expect(resultTwo.item.data.bountyAmount.toNumber(), 'Bounty includes the funding').equals(4 * LPS);
+			expect(await getAccountBalance(item.publicKey), 'Item holds the whole pool').equals(4 * LPS);
+		});
+
+		it('cannot fund an item past the funder limit', async () => {
+			const [owner, adder, lateFunder] = await createUsers(3);
+			const funders = await createUsers(MAX_FUNDERS);
+			const list = await createList(owner, 'list');
+			const { item } = await addItem({ list, user: adder, name: 'An item', bounty: LPS });
+			for (const funder of funders) {
+				await fundItem({ list, item, funder, amount: 1 });
+			}
+
+			try {
+				await fundItem({ list, item, funder: lateFunder, amount: 1 });
+				expect.fail('Funding past the funder limit should fail');
+			} catch (e) {
+				expect(e.error.errorCode.code).equals("TooManyFunders");
+			}
+
+			const result = await cancelItem({
+				list,
+				item,
+				itemCreator: adder,
+				user: owner,
+				remainingAccounts: await contributionAccounts(item.publicKey, funders.map((funder) => funder.publicKey)),
+			});
+			expect(result.list.data.lines.length, 'Every funder fits into one cancel').equals(0);
+		});
+
+		it('cannot cancel a funded item without every contribution', async () => {
+			const [owner, adder, funder, otherFunder] = await createUsers(4);
+			const list = await createList(owner, 'list');
+			const { item } = await addItem({ list, user: adder, name: 'An item', bounty: LPS });
+			await fundItem({ list, item, funder, amount: LPS });
+			await fundItem({ list, item, funder: otherFunder, amount: LPS });
+
+			try {
+				await cancelItem({
+					list,
+					item,
+					itemCreator: adder,
+					user: owner,
+					remainingAccounts: await contributionAccounts(item.publicKey, [funder.publicKey]),
+				});
+				expect.fail('Cancelling without every contribution should fail');
+			} catch (e) {
+				expect(e.error.errorCode.code).equals("MissingContributions");
+			}
+
+			expect(await getAccountBalance(item.publicKey), 'Item balance is unchanged').equals(3 * LPS);
+		});
+
+		it('can cancel a funded item: every funder is refunded', async () => {
+			const [owner, adder, funder] = await createUsers(3);
+			const list = await createList(owner, 'list');
+			const { item } = await addItem({ list, user: adder, name: 'An item', bounty: LPS });
+			await fundItem({ list, item, funder, amount: 2 * LPS });
+
+			const [contribution] = await contributionAccounts(item.publicKey, [funder.publicKey]);
+			const receiptRent = await getAccountBalance(contribution.pubkey);
+			const adderBalance = await getAccountBalance(adder.publicKey);
+			const funderBalance = await getAccountBalance(funder.publicKey);
+
+			const cancelResult = await cancelItem({
+				list,
+				item,
+				itemCreator: adder,
+				user: owner,
+				remainingAccounts: await contributionAccounts(item.publicKey, [funder.publicKey]),
+			});
+
+			expect(cancelResult.list.data.lines, 'Cancel removes item from list').deep.equals([]);
+			expect(await getAccountBalance(adder.publicKey), 'Creator gets their bounty back').equals(adderBalance + LPS);
+			expect(await getAccountBalance(funder.publicKey), 'Funder gets their contribution back').equals(funderBalance + 2 * LPS + receiptRent);
+			expect(await getAccountBalance(contribution.pubkey), 'Contribution receipt is closed').equals(0);
+		});
+
+		it('can finish a funded item: the pool is paid to the list owner', async () => {
+			const [owner, adder, funder] = await createUsers(3);
+			const list = await createList(owner, 'list');
+			const { item } = await addItem({ list, user: adder, name: 'An item', bounty: LPS });
+			await fundItem({ list, item, funder, amount: 2 * LPS });
+
+			await finishItem({ list, item, user: adder, listOwner: owner, expectAccountClosed: false });
+			const ownerBalance = await getAccountBalance(owner.publicKey);
+
+			await finishItem({
+				list,
+				item,
+				user: owner,
+				listOwner: owner,
+				expectAccountClosed: true,
+				remainingAccounts: await contributionAccounts(item.publicKey, [funder.publicKey]),
+			});
+
+			expectBalance(await getAccountBalance(owner.publicKey), ownerBalance + 3 * LPS, 'Pool transferred to owner');
+		});
+	});
+
+	describe('close lists', () => {
+		it('can close an empty list: rent returns to the owner', async () => {
+			const owner = await createUser();
+			const list = await createList(owner, 'list');
+			const listRent = await getAccountBalance(list.publicKey);
+			const ownerBalance = await getAccountBalance(owner.publicKey);
+
+			await closeList({ list, listOwner: owner });
+
+			expect(await getAccountBalance(list.publicKey), 'List is closed').equals(0);
+			expect(await getAccountBalance(owner.publicKey), 'Rent returns to owner').equals(ownerBalance + listRent);
+
+			const newList = await createList(owner, 'list');
+			expect(newList.publicKey.toString(), 'List name can be reused').equals(list.publicKey.toString());
+		});
+
+		it('cannot close a list: other user', async () => {
+			const [owner, otherUser] = await createUsers(2);
+			const list = await createList(owner, 'list');
+
+			try {
+				await closeList({ list, listOwner: otherUser });
+				expect.fail('Closing by another user should fail');
+			} catch (e) {
+				expect(e.error.errorCode.code).equals("WrongListOwner");
+			}
+		});
+
+		it('cannot close a list with items left', async () => {
+			const [owner, adder] = await createUsers(2);
+			const list = await createList(owner, 'list');
+			const { item } = await addItem({ list, user: adder, name: 'An item', bounty: LPS });
+			await addItem({ list, user: adder, name: 'Another item', bounty: LPS });
+
+			try {
+				await closeList({ list, listOwner: owner, items: [{ item, itemCreator: adder }] });
+				expect.fail('Closing a list with items left should fail');
+			} catch (e) {
+				expect(e.error.errorCode.code).equals("ListNotEmpty");
+			}
+
+			expect(await getAccountBalance(item.publicKey), 'Item balance is unchanged').equals(LPS);
+		});
+
+		it('can close a list with items: bounties return to the item creators', async () => {
+			const [owner, adder, otherAdder] = await createUsers(3);
+			const list = await createList(owner, 'list');
+			const { item } = await addItem({ list, user: adder, name: 'An item', bounty: LPS });
+			const { item: otherItem } = await addItem({ list, user: otherAdder, name: 'Another item', bounty: 2 * LPS });
+
+			const page = await pageAddress(list.publicKey, 0);
+			const pageRent = await getAccountBalance(page);
+			const adderBalance = await getAccountBalance(adder.publicKey);
+			const otherAdderBalance = await getAccountBalance(otherAdder.publicKey);
+
+			await closeList({
+				list,
+				listOwner: owner,
+				items: [{ item, itemCreator: adder }, { item: otherItem, itemCreator: otherAdder }],
+			});
+
+			expect(await getAccountBalance(list.publicKey), 'List is closed').equals(0);
+			expect(await getAccountBalance(page), 'Page is closed').equals(0);
+			expect(await getAccountBalance(adder.publicKey), 'Bounty and page rent return to adder').equals(adderBalance + LPS + pageRent);
+			expect(await getAccountBalance(otherAdder.publicKey), 'Bounty returns to other adder').equals(otherAdderBalance + 2 * LPS);
+		});
+	});
+
+	describe('resize lists', () => {
+		it('can grow a full list', async () => {
+			const owner = await createUser();
+			const list = await createList(owner, 'list', 1);
+			await addItem({ list, user: owner, name: 'Filler item', bounty: LPS });
+
+			const resized = await resizeList({ list, listOwner: owner, capacity: 2 });
+			expect(resized.data.capacity, 'Capacity is updated').equals(2);
+
+			const result = await addItem({ list: resized, user: owner, name: 'Another item', bounty: LPS });
+			expect(result.list.data.lines.length, 'Item is added to the grown list').equals(2);
+		});
+
+		it('can lift the cap of a list', async () => {
+			const owner = await createUser();
+			const list = await createList(owner, 'list', 1);
+			await addItem({ list, user: owner, name: 'Filler item', bounty: LPS });
+
+			const resized = await resizeList({ list, listOwner: owner, capacity: null });
+			expect(resized.data.capacity, 'Cap is lifted').equals(0);
+
+			const result = await addItem({ list: resized, user: owner, name: 'Another item', bounty: LPS });
+			expect(result.list.data.lines.length, 'Item is added past the old cap').equals(2);
+		});
+
+		it('cannot shrink a list below its item count', async () => {
+			const owner = await createUser();
+			const list = await createList(owner, 'list', 4);
+			await addItem({ list, user: owner, name: 'Filler item 1', bounty: LPS });
+			await addItem({ list, user: owner, name: 'Filler item 2', bounty: LPS });
+
+			try {
+				await resizeList({ list, listOwner: owner, capacity: 1 });
+				expect.fail('Shrinking below the item count should fail');
+			} catch (e) {
+				expect(e.error.errorCode.code).equals("CapacityTooSmall");
+			}
+		});
+	});
+
+	describe('pages', () => {
+		it('can add items past a full page: the next page is opened', async () => {
+			const [owner, adder] = await createUsers(2);
+			const list = await createList(owner, 'list', null);
+			for (let i = 0; i < PAGE_SIZE; i++) {
+				await addItem({ list, user: owner, name: `Item ${i}`, bounty: LPS / 100 });
+			}
+
+			const result = await addItem({ list, user: adder, name: 'Item on the next page', bounty: LPS });
+			expect(result.list.data.pageCount, 'Next page is opened').equals(2);
+			expect(result.list.data.len, 'Items on all pages are counted').equals(PAGE_SIZE + 1);
+
+			const page = await program.account.listPage.fetch(await pageAddress(list.publicKey, 1));
+			expect(page.payer.toString(), 'Adder opening the page pays for it').equals(adder.publicKey.toString());
+			expect(page.lines.slice(0, page.len), 'Item is on the next page').deep.equals([result.item.publicKey]);
+
+			const cancelResult = await cancelItem({ list, item: result.item, itemCreator: adder, user: adder });
+			expect(cancelResult.list.data.len, 'Item is removed from its page').equals(PAGE_SIZE);
+		});
+
+		it('cannot add an item to a page past the next page', async () => {
+			const owner = await createUser();
+			const list = await createList(owner, 'list');
+			const item = await nextItemAccount(list.publicKey);
+
+			try {
+				await program.methods.add(list.data.name, 'An item', new BN(LPS), null, null, EMPTY_HASH, 1)
+					.accounts({
+						list: list.publicKey,
+						listOwner: list.data.listOwner,
+						page: await pageAddress(list.publicKey, 1),
+						item: item.publicKey,
+						user: owner.publicKey,
+						config: await configAddress(),
+					})
+					.signers([owner])
+					.rpc();
+				expect.fail('Skipping a page should fail');
+			} catch (e) {
+				expect(e.error.errorCode.code).equals("WrongPage");
+			}
+		});
+
+		it('cannot use the page of another list', async () => {
+			const owner = await createUser();
+			const [list1, list2] = await Promise.all([createList(owner, 'list1'), createList(owner, 'list2')]);
+			const { item } = await addItem({ list: list1, user: owner, name: 'An item', bounty: LPS });
+			await addItem({ list: list2, user: owner, name: 'Another item', bounty: LPS });
+
+			try {
+				await program.methods.cancel(list2.data.name)
+					.accounts({
+						list: list2.publicKey,
+						listOwner: owner.publicKey,
+						page: await pageAddress(list1.publicKey, 0),
+						item: item.publicKey,
+						movedItem: item.publicKey,
+						itemCreator: owner.publicKey,
+						user: owner.publicKey,
+						moderator: await moderatorAccount(list2.publicKey, owner.publicKey),
+						config: await configAddress(),
+					})
+					.signers([owner])
+					.rpc();
+				expect.fail('Cancelling with the page of another list should fail');
+			} catch (e) {
+				expect(e.error.errorCode.code).equals("WrongPage");
+			}
+		});
+
+		it('cannot close a list without its pages', async () => {
+			const owner = await createUser();
+			const list = await createList(owner, 'list');
+			const { item } = await addItem({ list, user: owner, name: 'An item', bounty: LPS });
+			await cancelItem({ list, item, itemCreator: owner, user: owner });
+
+			try {
+				await program.methods.closeList(list.data.name)
+					.accounts({ list: list.publicKey, listOwner: owner.publicKey, config: await configAddress() })
+					.signers([owner])
+					.rpc();
+				expect.fail('Closing without the pages should fail');
+			} catch (e) {
+				expect(e.error.errorCode.code).equals("MissingPages");
+			}
+		});
+
+		it('can close the emptied last page: its rent returns to whoever opened it', async () => {
+			const [owner, adder] = await createUsers(2);
+			const list = await createList(owner, 'list', null);
+			for (let i = 0; i < PAGE_SIZE; i++) {
+				await addItem({ list, user: owner, name: `Item ${i}`, bounty: LPS / 100 });
+			}
+			const { item } = await addItem({ list, user: adder, name: 'Item on the next page', bounty: LPS });
+
+			try {
+				await closePage({ list, pageNo: 1 });
+				expect.fail('Closing a page with items should fail');
+			} catch (e) {
+				expect(e.error.errorCode.code).equals("PageNotEmpty");
+			}
+
+			try {
+				await closePage({ list, pageNo: 0 });
+				expect.fail('Closing a page before the last should fail');
+			} catch (e) {
+				expect(e.error.errorCode.code).equals("NotLastPage");
+			}
+
+			await cancelItem({ list, item, itemCreator: adder, user: adder });
+			const pageRent = await getAccountBalance(await pageAddress(list.publicKey, 1));
+			const adderBalance = await getAccountBalance(adder.publicKey);
+			await closePage({ list, pageNo: 1 });
+
+			expect(await getAccountBalance(adder.publicKey), 'Page rent returns to the adder').equals(adderBalance + pageRent);
+			expect((await fetchList(list.publicKey)).pageCount, 'Page is no longer counted').equals(1);
+		});
+	});
+
+	describe('transfer ownership', () => {
+		it('cannot accept a list: other user', async () => {
+			const [owner, newOwner, otherUser] = await createUsers(3);
+			const list = await createList(owner, 'list');
+			await proposeOwner({ list, listOwner: owner, newOwner: newOwner.publicKey });
+
+			try {
+				await acceptOwner({ list, newOwner: otherUser });
+				expect.fail('Accepting without a nomination should fail');
+			} catch (e) {
+				expect(e.error.errorCode.code).equals("NotPendingOwner");
+			}
+		});
+
+		it('cannot propose an owner: other user', async () => {
+			const [owner, otherUser] = await createUsers(2);
+			const list = await createList(owner, 'list');
+
+			try {
+				await proposeOwner({ list, listOwner: otherUser, newOwner: otherUser.publicKey });
+				expect.fail('Proposing by another user should fail');
+			} catch (e) {
+				expect(e.error.errorCode.code).equals("WrongListOwner");
+			}
+		});
+
+		it('can transfer a list: new owner takes over items at the same address', async () => {
+			const [owner, newOwner, adder] = await createUsers(3);
+			const list = await createList(owner, 'list');
+			const { item } = await addItem({ list, user: adder, name: 'An item', bounty: LPS });
+
+			await proposeOwner({ list, listOwner: owner, newOwner: newOwner.publicKey });
+			const transferred = await acceptOwner({ list, newOwner });
+
+			expect(transferred.publicKey.toString(), 'List keeps its address').equals(list.publicKey.toString());
+			expect(transferred.data.listOwner.toString(), 'Owner is updated').equals(newOwner.publicKey.toString());
+			expect(transferred.data.pendingOwner, 'Nomination is cleared').equals(null);
+
+			try {
+				await finishItem({ list: transferred, item, user: owner, listOwner: owner, expectAccountClosed: false });
+				expect.fail('Finish by the previous owner should fail');
+			} catch (e) {
+				expect(e.error.errorCode.code).equals("WrongListOwner");
+			}
+
+			const newOwnerBalance = await getAccountBalance(newOwner.publicKey);
+			await finishItem({ list: transferred, item, user: newOwner, listOwner: newOwner, expectAccountClosed: false });
+			await finishItem({ list: transferred, item, user: adder, listOwner: newOwner, expectAccountClosed: true });
+			expect(await getAccountBalance(newOwner.publicKey), 'Bounty transferred to new owner').equals(newOwnerBalance + LPS);
+
+			const result = await addItem({ list: transferred, user: adder, name: 'Another item', bounty: LPS });
+			expect(result.list.data.lines, 'Items can still be added').deep.equals([result.item.publicKey]);
+		});
+	});
+
+	describe('moderators', () => {
+		it('can cancel item: moderator with cancel role', async () => {
+			const [owner, adder, moderator] = await createUsers(3);
+			const list = await createList(owner, 'list');
+			const { item } = await addItem({ list, user: adder, name: 'An item', bounty: LPS });
+
+			const roles = await setModerator({ list, listOwner: owner, moderator, canCancel: true, canFinish: false });
+			expect(roles.canCancel, 'Cancel role is set').equals(true);
+			expect(roles.canFinish, 'Finish role is not set').equals(false);
+
+			const adderBalance = await getAccountBalance(adder.publicKey);
+			const cancelResult = await cancelItem({ list, item, itemCreator: adder, user: moderator });
+
+			expect(cancelResult.list.data.lines, 'Cancel removes item from list').deep.equals([]);
+			expect(await getAccountBalance(adder.publicKey), 'Cancel returns bounty to adder').equals(adderBalance + LPS);
+		});
+
+		it('cannot cancel item: moderator without cancel role', async () => {
+			const [owner, adder, moderator] = await createUsers(3);
+			const list = await createList(owner, 'list');
+			const { item } = await addItem({ list, user: adder, name: 'An item', bounty: LPS });
+			await setModerator({ list, listOwner: owner, moderator, canCancel: false, canFinish: true });
+
+			try {
+				await cancelItem({ list, item, itemCreator: adder, user: moderator });
+				expect.fail('Cancel without the cancel role should fail');
+			} catch (e) {
+				expect(e.error.errorCode.code).equals("ModeratorCannotCancel");
+			}
+		});
+
+		it('can finish item: moderator marks the owner side', async () => {
+			const [owner, adder, moderator] = await createUsers(3);
+			const list = await createList(owner, 'list');
+			const { item } = await addItem({ list, user: adder, name: 'An item', bounty: LPS });
+			await setModerator({ list, listOwner: owner, moderator, canCancel: false, canFinish: true });
+
+			const ownerBalance = await getAccountBalance(owner.publicKey);
+			const firstResult = await finishItem({ list, item, user: moderator, listOwner: owner, expectAccountClosed: false });
+			expect(firstResult.item.data.listOwnerFinished, 'Owner finish flag gets set by moderator').equals(true);
+
+			await finishItem({ list, item, user: adder, listOwner: owner, expectAccountClosed: true });
+			expect(await getAccountBalance(owner.publicKey), 'Bounty transferred to owner').equals(ownerBalance + LPS);
+		});
+
+		it('cannot finish item: removed moderator', async () => {
+			const [owner, adder, moderator] = await createUsers(3);
+			const list = await createList(owner, 'list');
+			const { item } = await addItem({ list, user: adder, name: 'An item', bounty: LPS });
+			await setModerator({ list, listOwner: owner, moderator, canCancel: true, canFinish: true });
+			await removeModerator({ list, listOwner: owner, moderator });
+
+			try {
+				await finishItem({ list, item, user: moderator, listOwner: owner, expectAccountClosed: false });
+				expect.fail('Finish by a removed moderator should fail');
+			} catch (e) {
+				expect(e.error.errorCode.code).equals("FinishPermissions");
+			}
+		});
+
+		it('cannot close a list: moderators left', async () => {
+			const [owner, moderator] = await createUsers(2);
+			const list = await createList(owner, 'list');
+			await setModerator({ list, listOwner: owner, moderator, canCancel: true, canFinish: false });
+			await setModerator({ list, listOwner: owner, moderator, canCancel: true, canFinish: true });
+			expect((await fetchList(list.publicKey)).moderatorCount.toNumber(), 'Replacing roles keeps the count').equals(1);
+
+			try {
+				await closeList({ list, listOwner: owner });
+				expect.fail('Closing with moderators left should fail');
+			} catch (e) {
+				expect(e.error.errorCode.code).equals("ModeratorsLeft");
+			}
+
+			await removeModerator({ list, listOwner: owner, moderator });
+			await closeList({ list, listOwner: owner });
+			expect(await getAccountBalance(list.publicKey), 'List is closed').equals(0);
+		});
+	});
+
+	describe('edit items', () => {
+		it('can rename an item: bounty is kept', async () => {
+			const [owner, adder] = await createUsers(2);
+			const list = await createList(owner, 'list');
+			const { item } = await addItem({ list, user: adder, name: 'A tpyo', bounty: LPS });
+
+			const longer = await editItem({ list, item, itemCreator: adder, name: 'A typo fixed with a much longer name' });
+			expect(longer.name, 'Name is updated').equals('A typo fixed with a much longer name');
+			expect(longer.bountyAmount.toNumber(), 'Bounty is unchanged').equals(LPS);
+
+			const shorter = await editItem({ list, item, itemCreator: adder, name: 'Short' });
+			expect(shorter.name, 'Name is updated').equals('Short');
+			expect(await getAccountBalance(item.publicKey), 'Item keeps its bounty').equals(LPS);
+		});
+
+		it('cannot edit an item: other user', async () => {
+			const [owner, adder] = await createUsers(2);
+			const list = await createList(owner, 'list');
+			const { item } = await addItem({ list, user: adder, name: 'An item', bounty: LPS });
+
+			try {
+				await editItem({ list, item, itemCreator: owner, name: 'Renamed' });
+				expect.fail('Editing by the list owner should fail');
+			} catch (e) {
+				expect(e.error.errorCode.code).equals("EditPermissions");
+			}
+		});
+
+		it('cannot edit an item once marked finished', async () => {
+			const [owner, adder] = await createUsers(2);
+			const list = await createList(owner, 'list');
+			const { item } = await addItem({ list, user: adder, name: 'An item', bounty: LPS });
+			await finishItem({ list, item, user: owner, listOwner: owner, expectAccountClosed: false });
+
+			try {
+				await editItem({ list, item, itemCreator: adder, name: 'Something else' });
+				expect.fail('Editing a finished item should fail');
+			} catch (e) {
+				expect(e.error.errorCode.code).equals("ItemLocked");
+			}
+		});
+
+		it('cannot edit an item once funded', async () => {
+			const [owner, adder, funder] = await createUsers(3);
+			const list = await createList(owner, 'list');
+			const { item } = await addItem({ list, user: adder, name: 'An item', bounty: LPS });
+			await fundItem({ list, item, funder, amount: LPS });
+
+			try {
+				await editItem({ list, item, itemCreator: adder, name: 'Something else' });
+				expect.fail('Editing a funded item should fail');
+			} catch (e) {
+				expect(e.error.errorCode.code).equals("ItemHasFunders");
+			}
+
+			expect((await program.account.listItem.fetch(item.publicKey)).name, 'Name is unchanged').equals('An item');
+		});
+	});
+
+	describe('item descriptions', () => {
+		it('can add an item with a description uri and content hash', async () => {
+			const [owner, adder] = await createUsers(2);
+			const list = await createList(owner, 'list');
+			const contentHash = Array.from(Buffer.alloc(32, 7));
+
+			const { item } = await addItem({
+				list,
+				user: adder,
+				name: 'An item',
+				bounty: LPS,
+				uri: 'ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi',
+				contentHash,
+			});
+
+			expect(item.data.uri, 'Uri is set').equals('ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi');
+			expect(item.data.contentHash, 'Content hash is set').deep.equals(contentHash);
+		});
+
+		it('cannot add an item with a uri that is too long', async () => {
+			const [owner, adder] = await createUsers(2);
+			const list = await createList(owner, 'list');
+
+			try {
+				await addItem({ list, user: adder, name: 'An item', bounty: LPS, uri: 'https://' + 'a'.repeat(200) });
+				expect.fail('Adding with a long uri should fail');
+			} catch (e) {
+				expect(e.error.errorCode.code).equals("InvalidUri");
+			}
+		});
+
+		it('can edit the description of an item', async () => {
+			const [owner, adder] = await createUsers(2);
+			const list = await createList(owner, 'list');
+			const { item } = await addItem({ list, user: adder, name: 'An item', bounty: LPS });
+			const contentHash = Array.from(Buffer.alloc(32, 1));
+
+			const edited = await editItem({ list, item, itemCreator: adder, name: 'An item', uri: 'https://example.com/spec.md', contentHash });
+			expect(edited.uri, 'Uri is updated').equals('https://example.com/spec.md');
+			expect(edited.contentHash, 'Content hash is updated').deep.equals(contentHash);
+		});
+	});
+
+	describe('events', () => {
+		it('emits ListCreated and ItemAdded', async () => {
+			const [owner, adder] = await createUsers(2);
+			let list;
+			let result;
+
+			const events = await captureEvents(['ListCreated', 'ItemAdded'], async () => {
+				list = await createList(owner, 'list');
+				result = await addItem({ list, user: adder, name: 'An item', bounty: LPS });
+			});
+
+			expect(events.map(({ name }) => name), 'Events are emitted in order').deep.equals(['ListCreated', 'ItemAdded']);
+			expect(events[0].event.list.toString(), 'ListCreated has the list').equals(list.publicKey.toString());
+			expect(events[1].event.item.toString(), 'ItemAdded has the item').equals(result.item.publicKey.toString());
+			expect(events[1].event.bounty.toNumber(), 'ItemAdded has the bounty').equals(LPS);
+		});
+
+		it('emits ItemFinishMarked for each side and ItemPaid', async () => {
+			const [owner, adder] = await createUsers(2);
+			const list = await createList(owner, 'list');
+			const { item } = await addItem({ list, user: adder, name: 'An item', bounty: LPS });
+
+			const events = await captureEvents(['ItemFinishMarked', 'ItemPaid'], async () => {
+				await finishItem({ list, item, user: owner, listOwner: owner, expectAccountClosed: false });
+				await finishItem({ list, item, user: adder, listOwner: owner, expectAccountClosed: true });
+			});
+
+			expect(events.map(({ name }) => name), 'Events are emitted in order').deep.equals(['ItemFinishMarked', 'ItemFinishMarked', 'ItemPaid']);
+			expect(events[0].event.side, 'List owner side is marked first').deep.equals({ listOwner: {} });
+			expect(events[1].event.side, 'Creator side is marked second').deep.equals({ creator: {} });
+			expect(events[2].event.recipient.toString(), 'Bounty is paid to the owner').equals(owner.publicKey.toString());
+			expect(events[2].event.amount.toNumber(), 'ItemPaid has the amount').equals(LPS);
+		});
+
+		it('emits ItemFinishRevoked for the revoked side', async () => {
+			const [owner, adder] = await createUsers(2);
+			const list = await createList(owner, 'list');
+			const { item } = await addItem({ list, user: adder, name: 'An item', bounty: LPS });
+			await finishItem({ list, item, user: adder, listOwner: owner, expectAccountClosed: false });
+
+			const events = await captureEvents(['ItemFinishRevoked'], () => unfinishItem({ list, item, user: adder }));
+
+			expect(events.length, 'One event is emitted').equals(1);
+			expect(events[0].event.side, 'Creator side is revoked').deep.equals({ creator: {} });
+			expect(events[0].event.revokedBy.toString(), 'ItemFinishRevoked has the revoker').equals(adder.publicKey.toString());
+		});
+
+		it('emits ItemCancelled with the refund recipient', async () => {
+			const [owner, adder] = await createUsers(2);
+			const list = await createList(owner, 'list');
+			const { item } = await addItem({ list, user: adder, name: 'An item', bounty: LPS });
+
+			const events = await captureEvents(['ItemCancelled'], () => cancelItem({ list, item, itemCreator: adder, user: owner }));
+
+			expect(events.length, 'One event is emitted').equals(1);
+			expect(events[0].event.cancelledBy.toString(), 'ItemCancelled has the canceller').equals(owner.publicKey.toString());
+			expect(events[0].event.refundRecipient.toString(), 'ItemCancelled has the refund recipient').equals(adder.publicKey.toString());
+		});
+
+		it('emits ItemEdited with the new description', async () => {
+			const [owner, adder] = await createUsers(2);
+			const list = await createList(owner, 'list');
+			const { item } = await addItem({ list, user: adder, name: 'An item', bounty: LPS });
+			const contentHash = Array.from(Buffer.alloc(32, 1));
+
+			const events = await captureEvents(['ItemEdited'], () =>
+				editItem({ list, item, itemCreator: adder, name: 'Renamed', uri: 'https://example.com/spec.md', contentHash }));
+
+			expect(events.length, 'One event is emitted').equals(1);
+			expect(events[0].event.name, 'ItemEdited has the new name').equals('Renamed');
+			expect(events[0].event.uri, 'ItemEdited has the new uri').equals('https://example.com/spec.md');
+			expect(events[0].event.contentHash, 'ItemEdited has the new content hash').deep.equals(contentHash);
+		});
+	});
+});